@@ -0,0 +1,60 @@
+//! Compares linting with the per-rule combined matcher against checking every case in turn,
+//! and linting with the memo of repeated basenames against checking each name afresh.
+//!
+//! Run with `cargo bench`.
+
+use fnlint::config::{CaseException, CaseOptions, FilenameCase, FilenameRule, UserRegex};
+use fnlint::linter::{lint_file, lint_files, RuleContext};
+use regex::Regex;
+use std::hint::black_box;
+use std::sync::Arc;
+use std::time::Instant;
+
+const FILES: usize = 200_000;
+
+fn main() {
+  let stems = ["index", "user_service", "userService", "UserService", "user-service", "USER"];
+  let files = (0..FILES)
+    .map(|index| format!("src/module{}/{}.ts", index % 100, stems[index % stems.len()]))
+    .collect::<Vec<String>>();
+  let rule = FilenameRule::from(vec![
+    FilenameCase::Kebab,
+    FilenameCase::Snake,
+    FilenameCase::Point,
+    FilenameCase::ScreamingSnake,
+  ]);
+
+  let start = Instant::now();
+  let options = CaseOptions::default();
+  let per_case = files
+    .iter()
+    .filter(|file| {
+      let stem = file.rsplit('/').next().unwrap().trim_end_matches(".ts");
+      !rule.cases.iter().any(|case| case.matches(black_box(stem), &options))
+    })
+    .count();
+  println!("per-case matching:  {:?} ({} issues)", start.elapsed(), per_case);
+
+  let start = Instant::now();
+  let combined = lint_files(black_box(files.clone()), ".ts".to_string(), &rule).len();
+  println!("lint_files:         {:?} ({} issues)", start.elapsed(), combined);
+  assert_eq!(per_case, combined);
+
+  // exceptions cost a regex search each before the cases are even tried
+  let exceptions =
+    ["^use[A-Z]", "^[A-Z][a-z]+Provider$", "^__.*__$"].map(|pattern| CaseException {
+      pattern: UserRegex(Regex::new(pattern).unwrap()),
+      cases: Arc::new(vec![FilenameCase::Camel, FilenameCase::Pascal]),
+    });
+  let rule = FilenameRule { exceptions: exceptions.to_vec(), ..rule };
+  let uncached = RuleContext::new(&rule, ".ts").without_memo();
+  let start = Instant::now();
+  let fresh = files.iter().flat_map(|file| lint_file(black_box(file), &uncached)).count();
+  println!("without memo:       {:?} ({} issues)", start.elapsed(), fresh);
+
+  let memoized = RuleContext::new(&rule, ".ts");
+  let start = Instant::now();
+  let remembered = files.iter().flat_map(|file| lint_file(black_box(file), &memoized)).count();
+  println!("with memo:          {:?} ({} issues)", start.elapsed(), remembered);
+  assert_eq!(fresh, remembered);
+}
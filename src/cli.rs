@@ -0,0 +1,198 @@
+use clap::{Parser, Subcommand, ValueEnum};
+use fnlint::config::{FilenameCase, PathStyle};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// The exit codes, listed at the end of `--help`.
+const EXIT_CODES: &str = "Exit codes:
+  0  no issues
+  1  issues found, files that could not be checked, or unused rules with --error-on-unused-rules
+  2  the config cannot be found or loaded, or the arguments are invalid
+  3  the scan hit --timeout
+  4  any other error, e.g. an unreadable scan root";
+
+#[derive(Debug, Parser)]
+#[command(version, about, after_help = EXIT_CODES)]
+pub struct Cli {
+  #[command(subcommand)]
+  pub command: Option<Command>,
+  /// Path to the configuration file, instead of discovering it in the working directory or the
+  /// nearest parent that has one.
+  /// Falls back to `FNLINT_CONFIG` when not given.
+  #[arg(long, env = "FNLINT_CONFIG")]
+  pub config: Option<PathBuf>,
+  /// Print, as JSON, every configuration source that was loaded and the keys it set.
+  #[arg(long)]
+  pub debug_config: bool,
+  /// Print issue paths relative to this directory. Defaults to the scan root.
+  #[arg(long, value_name = "DIR")]
+  pub relative_to: Option<PathBuf>,
+  /// Print issue paths relative to the root of the git repository, wherever fnlint runs in it.
+  /// Outside of a repository, paths stay relative to the scan root.
+  #[arg(long, conflicts_with = "relative_to")]
+  pub git_relative: bool,
+  /// Print issue paths `relative`, `absolute` or as `file://` URIs, instead of as the config's
+  /// `pathStyle` says.
+  #[arg(long, value_name = "STYLE", value_parser = PathStyle::from_str)]
+  pub path_style: Option<PathStyle>,
+  /// Check the sample names of the config's `tests` against its rules, reporting each one that
+  /// passes or fails unlike declared, instead of linting.
+  #[arg(long)]
+  pub config_test: bool,
+  /// Merge the reports of several `--format json` runs into one, without duplicates and sorted
+  /// by path, instead of linting.
+  #[arg(long, num_args = 1.., value_name = "FILE")]
+  pub merge_output: Vec<PathBuf>,
+  /// Rewrite the config into its canonical shape, every `ls` rule an object with each option
+  /// spelled out, and print it, or write it as JSON to FILE, instead of linting.
+  #[arg(long, value_name = "FILE", num_args = 0..=1, default_missing_value = "-")]
+  pub config_migrate: Option<PathBuf>,
+  /// Print a JSON Schema of the configuration file, e.g. for editor completion.
+  #[arg(long)]
+  pub config_schema: bool,
+  /// Print a suggested configuration based on the case most files already use.
+  #[arg(long)]
+  pub init_from_repo: bool,
+  /// Also lint files and directories whose name starts with a dot, such as `.github/`.
+  #[arg(long)]
+  pub hidden: bool,
+  /// Only lint files whose path contains this substring. Ignore rules still apply.
+  #[arg(long, value_name = "SUBSTR")]
+  pub path_filter: Option<String>,
+  /// Only lint the files staged in git, e.g. from a pre-commit hook.
+  #[arg(long, conflicts_with_all = ["files", "stdin_paths"])]
+  pub staged: bool,
+  /// Only lint these files instead of scanning the working directory. Ignore rules still apply.
+  #[arg(long, num_args = 1.., value_name = "PATH")]
+  pub files: Vec<String>,
+  /// Lint the files as they are in this git commit, tag or branch instead of the working tree,
+  /// with the rules of the current config.
+  #[arg(
+    long,
+    value_name = "REF",
+    conflicts_with_all = ["staged", "files", "stdin_paths", "fix", "autofix_case", "watch"]
+  )]
+  pub rev: Option<String>,
+  /// Only lint the files a JSON array of paths at PATH lists, as written by CI systems from their
+  /// own diff. Ignore rules still apply; listed paths that are no file are skipped with a note.
+  #[arg(
+    long,
+    value_name = "PATH",
+    conflicts_with_all = ["staged", "files", "stdin_paths", "rev", "watch"]
+  )]
+  pub changed_manifest: Option<PathBuf>,
+  /// Read the files to lint from stdin, one path per line.
+  #[arg(long, conflicts_with = "files")]
+  pub stdin_paths: bool,
+  /// Lint the entry names of a `.zip` or `.tar` archive instead of the working directory.
+  #[cfg(feature = "archive")]
+  #[arg(long, value_name = "PATH", conflicts_with_all = ["staged", "files", "stdin_paths"])]
+  pub archive: Option<PathBuf>,
+  /// Keep running and report files as they are created. With `--fix`, rename them right away.
+  #[arg(long, conflicts_with_all = ["staged", "files", "stdin_paths"])]
+  pub watch: bool,
+  /// In a GitHub Actions run for a pull request, also post each issue as a review comment on
+  /// its file. Reads `GITHUB_TOKEN`, `GITHUB_REPOSITORY` and `GITHUB_EVENT_PATH`.
+  #[cfg(feature = "github-review")]
+  #[arg(long)]
+  pub github_review: bool,
+  /// Fail when an `ls` rule matched no file at all, as left behind by stale config.
+  #[arg(long)]
+  pub error_on_unused_rules: bool,
+  /// Fail instead of warning when the config defines no `ls` rule at all, so nothing is checked.
+  #[arg(long)]
+  pub strict_config: bool,
+  /// Warn about scanned paths longer than this many bytes; the default is Linux's `PATH_MAX`.
+  #[arg(long, value_name = "BYTES", default_value_t = 4096)]
+  pub max_path_length: usize,
+  /// Stop scanning after this many seconds, report what was found so far and exit with 3.
+  #[arg(long, value_name = "SECONDS", conflicts_with = "watch")]
+  pub timeout: Option<u64>,
+  /// Rename files to the first case their rule expects.
+  #[arg(long)]
+  pub fix: bool,
+  /// Rename every file to this case, ignoring the rules, e.g. for a one-time migration. Combine
+  /// with `--format diff` to print the renames instead.
+  #[arg(long, value_name = "CASE", value_parser = FilenameCase::from_str)]
+  pub autofix_case: Option<FilenameCase>,
+  /// Check every file an `ls` rule covers against this case instead of the rule's own, e.g. to
+  /// try out a convention. Files no rule covers are still skipped, and nothing is renamed.
+  #[arg(long, value_name = "CASE", value_parser = FilenameCase::from_str)]
+  pub enforce: Option<FilenameCase>,
+  /// Leave out the issues recorded in this baseline file, so only new ones are reported.
+  #[arg(long, value_name = "FILE")]
+  pub baseline: Option<PathBuf>,
+  /// Record every issue found in this baseline file, for later runs with `--baseline`, instead
+  /// of reporting them.
+  #[arg(long, value_name = "FILE", conflicts_with = "baseline")]
+  pub write_baseline: Option<PathBuf>,
+  /// How to print the results.
+  #[arg(long, value_enum, default_value_t = Format::Text)]
+  pub format: Format,
+  /// Print how often each kind of violation occurs, most common first, instead of the issues.
+  #[arg(long)]
+  pub stats: bool,
+  /// Print only the directories holding issues, as `path/ (N issues)` lines, most issues first.
+  #[arg(long, conflicts_with = "stats")]
+  pub dirs_summary: bool,
+  /// Indent JSON output. Has no effect on line-delimited formats such as ndjson.
+  #[arg(long)]
+  pub pretty: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+  /// Check the configuration file for problems and print a health report.
+  Doctor,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, ValueEnum)]
+pub enum Format {
+  /// One line per issue.
+  Text,
+  /// With `--fix` or `--autofix-case`, print the renames as a `git mv` script instead of
+  /// performing them.
+  Diff,
+  /// A JSON document listing every issue.
+  Json,
+  /// An indented JSON document with the current and expected case and the suggested name of
+  /// each issue, for reading by people and tools alike.
+  PrettyJson,
+  /// One JSON object per issue and line.
+  Ndjson,
+  /// An aligned table of path, detected case and expected cases.
+  Table,
+  /// A Code Climate report, for GitLab CI code quality widgets.
+  Codeclimate,
+  /// A Checkstyle XML report, as read by Jenkins and other CI servers.
+  Checkstyle,
+  /// TeamCity service messages, reported as inspections of the build.
+  Teamcity,
+  /// Only a line counting the issues and the files they are in, for dashboards that record
+  /// pass or fail, which the exit code tells.
+  SummaryOnly,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use fnlint::config::FilenameLintConfig;
+  use std::fs;
+
+  #[test]
+  fn config_from_env() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("ci.json");
+    fs::write(&path, r#"{ "ls": { ".rs": ["snake_case"] }, "ignore": ["target"] }"#).unwrap();
+    std::env::set_var("FNLINT_CONFIG", &path);
+
+    let cli = Cli::parse_from(["fnlint"]);
+    assert_eq!(cli.config.as_deref(), Some(path.as_path()));
+    let (config, _) = FilenameLintConfig::load_traced(&cli.config.unwrap()).unwrap();
+    assert_eq!(config.ignore, vec!["target"]);
+
+    let cli = Cli::parse_from(["fnlint", "--config", "explicit.json"]);
+    assert_eq!(cli.config, Some(PathBuf::from("explicit.json")));
+    std::env::remove_var("FNLINT_CONFIG");
+  }
+}
@@ -0,0 +1,155 @@
+use crate::config::FilenameCase;
+
+/// Split a name into lowercase words at `_`, `-`, `.`, whitespace and before every capital
+/// letter. Digits stay with the word in front of them.
+pub fn tokenize(name: &str) -> Vec<String> {
+  tokenize_with(name, &[])
+}
+
+/// [`tokenize`], keeping each of `acronyms`, e.g. `GRPC`, together as one word where it is
+/// written as is and not followed by a lowercase letter, so `GRPCClient` gives `grpc` and
+/// `client`.
+pub fn tokenize_with(name: &str, acronyms: &[String]) -> Vec<String> {
+  let mut words: Vec<String> = vec![];
+  let mut current = String::new();
+  let mut skip_to = 0;
+  for (index, c) in name.char_indices() {
+    if index < skip_to {
+      continue;
+    }
+    if c == '_' || c == '-' || c == '.' || c.is_whitespace() {
+      if !current.is_empty() {
+        words.push(std::mem::take(&mut current));
+      }
+      continue;
+    }
+    if let Some(acronym) = c.is_uppercase().then(|| acronym_at(&name[index..], acronyms)).flatten()
+    {
+      if !current.is_empty() {
+        words.push(std::mem::take(&mut current));
+      }
+      current = acronym.to_lowercase();
+      skip_to = index + acronym.len();
+      continue;
+    }
+    if c.is_uppercase() && !current.is_empty() {
+      words.push(std::mem::take(&mut current));
+    }
+    current.extend(c.to_lowercase());
+  }
+  if !current.is_empty() {
+    words.push(current);
+  }
+  words
+}
+
+/// The longest of `acronyms` that `rest` starts with, unless a lowercase letter follows it.
+fn acronym_at<'a>(rest: &str, acronyms: &'a [String]) -> Option<&'a str> {
+  acronyms
+    .iter()
+    .filter(|acronym| !acronym.is_empty() && rest.starts_with(acronym.as_str()))
+    .filter(|acronym| !rest[acronym.len()..].starts_with(char::is_lowercase))
+    .max_by_key(|acronym| acronym.len())
+    .map(String::as_str)
+}
+
+fn capitalize(word: &str) -> String {
+  let mut chars = word.chars();
+  match chars.next() {
+    Some(first) => first.to_uppercase().chain(chars).collect(),
+    None => String::new(),
+  }
+}
+
+/// Split `basename` at its first dot into the stem that case conversions rewrite and the rest,
+/// extension and qualifiers such as `.test` included, that they keep: `userService.test.ts`
+/// gives `userService` and `.test.ts`. A dotfile like `.eslintrc` has an empty stem.
+pub fn split_stem(basename: &str) -> (&str, &str) {
+  basename.split_at(basename.find('.').unwrap_or(basename.len()))
+}
+
+/// Rewrite `name` in the case `to`, with the same tokenizer `--fix` uses.
+///
+/// Only the [stem](split_stem) is converted, so what follows the first dot is kept as is and a
+/// dotfile is returned unchanged. Since that dot ends the stem, pass a `point.case` stem to
+/// [`FilenameCase::convert`] instead.
+///
+/// ```
+/// use fnlint::config::FilenameCase;
+/// use fnlint::convert_case;
+///
+/// assert_eq!(convert_case("helloWorld", FilenameCase::Lower), "helloworld");
+/// assert_eq!(convert_case("helloWorld", FilenameCase::Snake), "hello_world");
+/// assert_eq!(convert_case("helloWorld", FilenameCase::Kebab), "hello-world");
+/// assert_eq!(convert_case("helloWorld", FilenameCase::Point), "hello.world");
+/// assert_eq!(convert_case("helloWorld", FilenameCase::Camel), "helloWorld");
+/// assert_eq!(convert_case("helloWorld", FilenameCase::Pascal), "HelloWorld");
+/// assert_eq!(convert_case("helloWorld", FilenameCase::ScreamingSnake), "HELLO_WORLD");
+/// assert_eq!(convert_case("helloWorld.test.ts", FilenameCase::Kebab), "hello-world.test.ts");
+/// assert_eq!(convert_case(".eslintrc", FilenameCase::Pascal), ".eslintrc");
+/// ```
+pub fn convert_case(name: &str, to: FilenameCase) -> String {
+  match split_stem(name) {
+    ("", _) => name.to_string(),
+    (stem, rest) => format!("{}{}", to.convert(stem), rest),
+  }
+}
+
+impl FilenameCase {
+  /// Rewrite a stem, e.g. `helloWorld`, in this case.
+  pub fn convert(&self, stem: &str) -> String {
+    self.convert_with(stem, &[])
+  }
+
+  /// [`convert`](Self::convert), splitting the stem with [`tokenize_with`].
+  pub fn convert_with(&self, stem: &str, acronyms: &[String]) -> String {
+    let words = tokenize_with(stem, acronyms);
+    match self {
+      FilenameCase::Lower => words.concat(),
+      FilenameCase::Snake => words.join("_"),
+      FilenameCase::Kebab => words.join("-"),
+      FilenameCase::Point => words.join("."),
+      FilenameCase::ScreamingSnake => words.join("_").to_uppercase(),
+      FilenameCase::Pascal => words.iter().map(|word| capitalize(word)).collect(),
+      FilenameCase::Camel => words
+        .iter()
+        .enumerate()
+        .map(|(index, word)| if index == 0 { word.clone() } else { capitalize(word) })
+        .collect(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_tokenize() {
+    assert_eq!(tokenize("helloWorld"), vec!["hello", "world"]);
+    assert_eq!(tokenize("Hello_World-again.now"), vec!["hello", "world", "again", "now"]);
+    assert_eq!(tokenize("base64Encoder"), vec!["base64", "encoder"]);
+    assert_eq!(tokenize("GRPCClient"), vec!["g", "r", "p", "c", "client"]);
+  }
+
+  #[test]
+  fn test_known_acronyms() {
+    let acronyms = ["GRPC", "IO", "UUID"].map(String::from);
+    assert_eq!(FilenameCase::Snake.convert("GRPCClient"), "g_r_p_c_client");
+    assert_eq!(FilenameCase::Snake.convert_with("GRPCClient", &acronyms), "grpc_client");
+    assert_eq!(FilenameCase::Kebab.convert_with("fileIOStream", &acronyms), "file-io-stream");
+    assert_eq!(FilenameCase::Camel.convert_with("UUID4Parser", &acronyms), "uuid4Parser");
+    assert_eq!(tokenize_with("IOta", &acronyms), vec!["i", "ota"]);
+  }
+
+  #[test]
+  fn test_convert() {
+    assert_eq!(FilenameCase::Lower.convert("helloWorld"), "helloworld");
+    assert_eq!(FilenameCase::Snake.convert("helloWorld"), "hello_world");
+    assert_eq!(FilenameCase::Kebab.convert("HelloWorld"), "hello-world");
+    assert_eq!(FilenameCase::Point.convert("hello_world"), "hello.world");
+    assert_eq!(FilenameCase::Camel.convert("hello-world"), "helloWorld");
+    assert_eq!(FilenameCase::Pascal.convert("hello_world"), "HelloWorld");
+    assert_eq!(FilenameCase::ScreamingSnake.convert("helloWorld"), "HELLO_WORLD");
+  }
+}
@@ -0,0 +1,146 @@
+use crate::config::merge::resolve;
+use crate::config::{FilenameLintConfig, CONFIG_FILES};
+use serde_json::{json, Value};
+use std::fmt::Display;
+use std::path::Path;
+
+#[derive(Debug, PartialEq)]
+pub enum Level {
+  Error,
+  Warning,
+}
+
+/// One problem found by [`diagnose`].
+#[derive(Debug, PartialEq)]
+pub struct Finding {
+  pub level: Level,
+  pub message: String,
+}
+
+impl Finding {
+  fn error(message: String) -> Self {
+    Finding { level: Level::Error, message }
+  }
+
+  fn warning(message: String) -> Self {
+    Finding { level: Level::Warning, message }
+  }
+}
+
+impl Display for Finding {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self.level {
+      Level::Error => write!(f, "error: {}", self.message),
+      Level::Warning => write!(f, "warning: {}", self.message),
+    }
+  }
+}
+
+/// Check the config at `path` piece by piece, so that every problem is reported instead of only
+/// the first one that stops the config from loading.
+pub fn diagnose(path: &Path) -> Vec<Finding> {
+  let mut findings = vec![];
+  let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+  if !CONFIG_FILES.contains(&name) {
+    findings.push(Finding::warning(format!(
+      "{} is not discovered automatically; name it one of {}",
+      path.display(),
+      CONFIG_FILES.join(", ")
+    )));
+  }
+  let value = match resolve(path, FilenameLintConfig::load_value) {
+    Ok((value, _)) => value,
+    Err(error) => {
+      findings.push(Finding::error(format!("Cannot load {}: {}", path.display(), error)));
+      return findings;
+    }
+  };
+
+  let rules = value.get("ls").and_then(Value::as_object).cloned().unwrap_or_default();
+  if rules.is_empty() {
+    findings.push(Finding::warning("`ls` has no rules, so no file is checked".to_string()));
+  }
+  for (ext, rule) in &rules {
+    let single = json!({ "ls": { ext: rule }, "ignore": [] });
+    match serde_json::from_value::<FilenameLintConfig>(single) {
+      Ok(config) if config.ls[ext].cases.is_empty() => {
+        findings.push(Finding::warning(format!("Rule {} lists no cases, so it never passes", ext)))
+      }
+      Ok(_) => {}
+      Err(error) => findings.push(Finding::error(format!("Rule {}: {}", ext, error))),
+    }
+  }
+
+  if let Some(marker) = value.get("generatedMarker").and_then(Value::as_str) {
+    if let Err(error) = regex::Regex::new(marker) {
+      findings.push(Finding::error(format!("generatedMarker does not compile: {}", error)));
+    }
+  }
+
+  for key in ["ignore", "include"] {
+    let patterns = value.get(key).and_then(Value::as_array).cloned().unwrap_or_default();
+    for pattern in patterns {
+      let Some(pattern) = pattern.as_str() else {
+        findings.push(Finding::error(format!("{} entry {} is not a string", key, pattern)));
+        continue;
+      };
+      if pattern.trim().is_empty() {
+        findings.push(Finding::error(format!("{} has an empty pattern", key)));
+      } else if pattern.trim() != pattern {
+        findings
+          .push(Finding::warning(format!("{} pattern {:?} has surrounding spaces", key, pattern)));
+      } else if pattern.contains('\\') {
+        findings.push(Finding::warning(format!(
+          "{} pattern {:?} uses `\\`; paths are matched with `/`",
+          key, pattern
+        )));
+      }
+    }
+  }
+
+  // anything not covered above, e.g. a missing `ignore` or a mistyped option
+  if findings.iter().all(|finding| finding.level != Level::Error) {
+    if let Err(error) = serde_json::from_value::<FilenameLintConfig>(value) {
+      findings.push(Finding::error(error.to_string()));
+    }
+  }
+  findings
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::fs;
+
+  #[test]
+  fn test_broken_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("lint.json");
+    fs::write(
+      &path,
+      r#"{
+        "ls": { ".ts": [], ".rs": ["snaky_case"] },
+        "ignore": ["", " target", "src\\gen"],
+        "generatedMarker": "(@generated"
+      }"#,
+    )
+    .unwrap();
+    let report = diagnose(&path).iter().map(|finding| finding.to_string()).collect::<Vec<_>>();
+    assert_eq!(report.len(), 7, "{:#?}", report);
+    assert!(report[0].starts_with("warning: ") && report[0].contains("is not discovered"));
+    assert_eq!(report[1], "warning: Rule .ts lists no cases, so it never passes");
+    assert!(report[2].starts_with("error: Rule .rs: Unknown filename case: \"snaky_case\""));
+    assert!(report[3].starts_with("error: generatedMarker does not compile"));
+    assert_eq!(report[4], "error: ignore has an empty pattern");
+    assert_eq!(report[5], r#"warning: ignore pattern " target" has surrounding spaces"#);
+    assert!(report[6].starts_with(r#"warning: ignore pattern "src\\gen" uses `\`"#));
+  }
+
+  #[test]
+  fn test_healthy_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("fnlint.config.json");
+    fs::write(&path, r#"{ "ls": { ".rs": ["snake_case"] }, "ignore": ["target"] }"#).unwrap();
+    assert_eq!(diagnose(&path), vec![]);
+  }
+}
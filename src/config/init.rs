@@ -0,0 +1,71 @@
+use crate::config::FilenameCase;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Directories skipped when bootstrapping a config, since no `ignore` list exists yet.
+pub const DEFAULT_IGNORE: [&str; 3] = [".git", "node_modules", "target"];
+
+/// Propose a config mapping each extension to the case most of its files already use.
+///
+/// Single-word stems like `mod` fit every lowercase case, so they only decide the outcome for
+/// extensions where no file has a more telling name. Ties go to the case listed first in
+/// [`FilenameCase::ALL`].
+pub fn suggest_config(files: &[String]) -> Value {
+  let mut counts: BTreeMap<String, [usize; FilenameCase::ALL.len()]> = BTreeMap::new();
+  for path in files {
+    let path = Path::new(path);
+    let (Some(stem), Some(ext)) = (path.file_stem(), path.extension()) else {
+      continue;
+    };
+    let Some(case) = FilenameCase::detect(stem.to_str().unwrap()) else {
+      continue;
+    };
+    let index = FilenameCase::ALL.iter().position(|c| *c == case).unwrap();
+    counts.entry(format!(".{}", ext.to_str().unwrap())).or_default()[index] += 1;
+  }
+
+  let mut ls = Map::new();
+  for (ext, counts) in counts {
+    let lower = FilenameCase::ALL.iter().position(|c| *c == FilenameCase::Lower).unwrap();
+    let dominant = (0..counts.len())
+      .filter(|index| *index != lower && counts[*index] > 0)
+      .max_by_key(|index| (counts[*index], std::cmp::Reverse(*index)))
+      .unwrap_or(lower);
+    ls.insert(ext, json!([FilenameCase::ALL[dominant].to_string()]));
+  }
+  json!({ "ls": ls, "ignore": DEFAULT_IGNORE })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn files(files: &[&str]) -> Vec<String> {
+    files.iter().map(|file| file.to_string()).collect()
+  }
+
+  #[test]
+  fn suggest_dominant_case() {
+    let config = suggest_config(&files(&[
+      "src/main.rs",
+      "src/linter/mod.rs",
+      "src/linter/lint_name.rs",
+      "src/scan/scan_dir.rs",
+      "src/config/FilenameCase.rs",
+      "web/index.ts",
+      "web/userService.ts",
+      "web/orderService.ts",
+      "web/README",
+    ]));
+    assert_eq!(config["ls"][".rs"], json!(["snake_case"]));
+    assert_eq!(config["ls"][".ts"], json!(["camelCase"]));
+    assert!(config["ls"].get("README").is_none());
+  }
+
+  #[test]
+  fn suggest_lowercase_for_single_words() {
+    let config = suggest_config(&files(&["docs/intro.md", "docs/usage.md"]));
+    assert_eq!(config["ls"][".md"], json!(["lowercase"]));
+  }
+}
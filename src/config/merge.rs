@@ -0,0 +1,102 @@
+use anyhow::{bail, Result};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+
+/// One configuration file that took part in resolving the final config.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ConfigSource {
+  pub path: PathBuf,
+  /// JSON pointers of the keys this source set, e.g. `/ignore` or `/ls/.rs`.
+  pub keys: Vec<String>,
+  /// The subset of `keys` that replaced a value set by an earlier source.
+  pub overridden: Vec<String>,
+}
+
+/// The configuration sources in the order they were applied, base configs first.
+#[derive(Debug, Default, Serialize, PartialEq)]
+pub struct ConfigTrace {
+  pub sources: Vec<ConfigSource>,
+}
+
+/// Load `path` together with the chain of configs it `extends`, and merge them into one value.
+///
+/// A config may name a base config (relative to itself) under `extends`. The base is applied
+/// first, then every key of the extending config replaces the base's, except for `ls` whose
+/// rules are merged per key.
+pub fn resolve(path: &Path, load: fn(&Path) -> Result<Value>) -> Result<(Value, ConfigTrace)> {
+  let mut chain = vec![];
+  let mut next = Some(path.to_path_buf());
+  while let Some(path) = next {
+    if chain.iter().any(|(seen, _)| seen == &path) {
+      bail!("Circular `extends` in {}", path.display());
+    }
+    let mut value = load(&path)?;
+    next = match value.as_object_mut().and_then(|object| object.remove("extends")) {
+      Some(Value::String(base)) => Some(path.parent().unwrap_or(Path::new(".")).join(base)),
+      Some(_) => bail!("`extends` in {} must be a path", path.display()),
+      None => None,
+    };
+    chain.push((path, value));
+  }
+
+  let mut merged = Map::new();
+  let mut trace = ConfigTrace::default();
+  for (path, value) in chain.into_iter().rev() {
+    let Value::Object(object) = value else {
+      bail!("Configuration {} is not an object", path.display());
+    };
+    let mut source = ConfigSource { path, keys: vec![], overridden: vec![] };
+    for (key, value) in object {
+      match (key.as_str(), value) {
+        ("ls", Value::Object(rules)) => {
+          let entry = merged.entry("ls").or_insert_with(|| Value::Object(Map::new()));
+          let Value::Object(base) = entry else { unreachable!() };
+          for (rule, value) in rules {
+            source.record(format!("/ls/{}", rule), base.insert(rule, value).is_some());
+          }
+        }
+        (_, value) => source.record(format!("/{}", key), merged.insert(key, value).is_some()),
+      }
+    }
+    trace.sources.push(source);
+  }
+  Ok((Value::Object(merged), trace))
+}
+
+impl ConfigSource {
+  fn record(&mut self, key: String, overridden: bool) {
+    if overridden {
+      self.overridden.push(key.clone());
+    }
+    self.keys.push(key);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::fs;
+
+  fn load_json(path: &Path) -> Result<Value> {
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+  }
+
+  #[test]
+  fn trace_two_sources() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("base.json"), r#"{ "ls": { ".rs": ["kebab-case"] }, "ignore": [] }"#)
+      .unwrap();
+    let child = dir.path().join("fnlint.config.json");
+    fs::write(&child, r#"{ "extends": "base.json", "ls": { ".rs": ["snake_case"], ".ts": [] } }"#)
+      .unwrap();
+
+    let (value, trace) = resolve(&child, load_json).unwrap();
+    assert_eq!(value["ls"][".rs"][0], "snake_case");
+    assert_eq!(value["ignore"], Value::Array(vec![]));
+    assert_eq!(trace.sources.len(), 2);
+    assert_eq!(trace.sources[0].path, dir.path().join("base.json"));
+    assert_eq!(trace.sources[1].keys, vec!["/ls/.rs", "/ls/.ts"]);
+    assert_eq!(trace.sources[1].overridden, vec!["/ls/.rs"]);
+  }
+}
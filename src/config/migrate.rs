@@ -0,0 +1,149 @@
+use crate::config::{deserialize_map, dotted, FilenameLintConfig, FilenameRule};
+use crate::linter::visitor::is_glob;
+use anyhow::{Context, Result};
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::path::Path;
+
+#[derive(Deserialize)]
+struct Ls(#[serde(deserialize_with = "deserialize_map")] IndexMap<String, FilenameRule>);
+
+/// Read the config at `path`, without following `extends`, and [`migrate`] it.
+pub fn migrate_file(path: &Path) -> Result<Value> {
+  migrate(FilenameLintConfig::load_value(path)?)
+}
+
+/// Rewrite a config into its canonical shape: every `ls` rule becomes an object listing its
+/// cases, in their canonical spelling, and each of its options, extension keys and extensions
+/// gain their leading dot, and inline roots are migrated the same way. Other keys are kept as
+/// they are, so the result loads into the same config.
+pub fn migrate(mut config: Value) -> Result<Value> {
+  let Value::Object(object) = &mut config else {
+    return Ok(config);
+  };
+  let categories = match object.get_mut("categories") {
+    Some(Value::Object(categories)) => {
+      for exts in categories.values_mut() {
+        dot_all(exts);
+      }
+      categories.keys().cloned().collect()
+    }
+    _ => vec![],
+  };
+  let key = |key: &String| match is_glob(key) || categories.contains(key) {
+    true => key.clone(),
+    false => dotted(key.clone()),
+  };
+  if let Some(ls) = object.get("ls") {
+    let Ls(rules) = Ls::deserialize(ls).context("Invalid `ls`")?;
+    let ls = rules.iter().map(|(k, rule)| (key(k), rule_value(rule))).collect::<Map<_, _>>();
+    object.insert("ls".into(), Value::Object(ls));
+  }
+  if let Some(Value::Object(tests)) = object.get_mut("tests") {
+    *tests = std::mem::take(tests).into_iter().map(|(k, test)| (key(&k), test)).collect();
+  }
+  if let Some(exts) = object.get_mut("allowedExtensions") {
+    dot_all(exts);
+  }
+  if let Some(Value::Array(roots)) = object.get_mut("roots") {
+    for root in roots {
+      if let Some(config) = root.get_mut("config").filter(|config| config.is_object()) {
+        *config = migrate(std::mem::take(config))?;
+      }
+    }
+  }
+  Ok(config)
+}
+
+fn dot_all(exts: &mut Value) {
+  if let Value::Array(exts) = exts {
+    for ext in exts {
+      if let Value::String(name) = ext {
+        *name = dotted(std::mem::take(name));
+      }
+    }
+  }
+}
+
+fn rule_value(rule: &FilenameRule) -> Value {
+  let cases = |cases: &[_]| cases.iter().map(ToString::to_string).collect::<Vec<_>>();
+  let exceptions = rule
+    .exceptions
+    .iter()
+    .map(|exception| json!({ "pattern": exception.pattern.0.as_str(), "cases": cases(&exception.cases) }))
+    .collect::<Vec<_>>();
+  json!({
+    "cases": cases(&rule.cases),
+    "allowNumericOnly": rule.allow_numeric_only,
+    "allowSingleWord": rule.allow_single_word,
+    "allowDotsInStem": rule.allow_dots_in_stem,
+    "allowQualifiers": rule.allow_qualifiers,
+    "localeSuffix": rule.locale_suffix,
+    "allowAcronyms": rule.case_options.allow_acronyms,
+    "allowLeadingDigit": rule.case_options.allow_leading_digit,
+    "allowTrailingDigit": rule.case_options.allow_trailing_digit,
+    "strictCapitals": rule.case_options.strict_capitals,
+    "message": rule.message.as_deref(),
+    "fullPath": rule.full_path,
+    "allowAcronymDirs": rule.allow_acronym_dirs,
+    "skipDirs": rule.skip_dirs,
+    "exceptions": exceptions,
+    "ignore": rule.ignore,
+    "requireExtension": rule.require_extension,
+    "includeExtension": rule.include_extension,
+    "severity": rule.severity.to_string(),
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn migrate_old_style_config() {
+    let old = json!({
+      "ls": { "rs": "snake", ".ts": ["kebab", "camelcase"], "style": "kebab-case" },
+      "categories": { "style": ["css", ".scss"] },
+      "allowedExtensions": ["rs"],
+      "ignore": ["target"],
+    });
+    let migrated = migrate(old).unwrap();
+    let rule = |cases: &[&str]| {
+      json!({
+        "cases": cases,
+        "allowNumericOnly": true,
+        "allowSingleWord": true,
+        "allowDotsInStem": true,
+        "allowQualifiers": [],
+        "localeSuffix": false,
+        "allowAcronyms": false,
+        "allowLeadingDigit": false,
+        "allowTrailingDigit": false,
+        "strictCapitals": false,
+        "message": null,
+        "fullPath": false,
+        "allowAcronymDirs": 0,
+        "skipDirs": 0,
+        "exceptions": [],
+        "ignore": [],
+        "requireExtension": false,
+        "includeExtension": false,
+        "severity": "error",
+      })
+    };
+    let expected = json!({
+      "ls": {
+        ".rs": rule(&["snake_case"]),
+        ".ts": rule(&["kebab-case", "camelCase"]),
+        "style": rule(&["kebab-case"]),
+      },
+      "categories": { "style": [".css", ".scss"] },
+      "allowedExtensions": [".rs"],
+      "ignore": ["target"],
+    });
+    assert_eq!(migrated, expected);
+    let config = serde_json::from_value::<FilenameLintConfig>(migrated).unwrap();
+    assert_eq!(config.ls.keys().collect::<Vec<_>>(), [".rs", ".ts", "style"]);
+  }
+}
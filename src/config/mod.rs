@@ -1,15 +1,35 @@
-use anyhow::Result;
-use regex::Regex;
+use crate::config::merge::{resolve, ConfigTrace};
+use crate::linter::visitor::is_glob;
+use crate::scan::pattern::Pattern;
+use anyhow::{bail, Result};
+use indexmap::IndexMap;
+use regex::{Regex, RegexBuilder};
+use schemars::JsonSchema;
 use serde::de::Visitor;
 use serde::{de, Deserialize, Deserializer};
 use serde_json::Value;
+use std::cell::Cell;
 use std::collections::HashMap;
 use std::fmt::Display;
-use std::path::Path;
+use std::path::{absolute, Path, PathBuf};
 use std::str::FromStr;
-use std::sync::LazyLock;
+use std::sync::{Arc, LazyLock};
 
-#[derive(Debug, PartialEq, Copy, Clone, Deserialize)]
+pub mod convert;
+pub mod doctor;
+pub mod init;
+pub mod merge;
+pub mod migrate;
+pub mod schema;
+
+/// The names a configuration file is discovered under, in order of preference.
+pub const CONFIG_FILES: [&str; 3] =
+  ["fnlint.config.json", "fnlint.config.yaml", "fnlint.config.toml"];
+
+/// A file marking its directory as the top of a project, above which no config is looked for.
+pub const ROOT_MARKER: &str = ".fnlint-root";
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Deserialize)]
 pub enum FilenameCase {
   Lower,
   Snake,
@@ -20,18 +40,37 @@ pub enum FilenameCase {
   ScreamingSnake,
 }
 
+/// Every spelling a case is accepted under, the canonical one printed by `Display` first.
+pub(crate) const SPELLINGS: [(&str, FilenameCase); 18] = [
+  ("lowercase", FilenameCase::Lower),
+  ("lower", FilenameCase::Lower),
+  ("snake_case", FilenameCase::Snake),
+  ("snake", FilenameCase::Snake),
+  ("camelCase", FilenameCase::Camel),
+  ("camel", FilenameCase::Camel),
+  ("camelcase", FilenameCase::Camel),
+  ("kebab-case", FilenameCase::Kebab),
+  ("kebab", FilenameCase::Kebab),
+  ("Pascal", FilenameCase::Pascal),
+  ("PascalCase", FilenameCase::Pascal),
+  ("pascal", FilenameCase::Pascal),
+  ("pascalcase", FilenameCase::Pascal),
+  ("point.case", FilenameCase::Point),
+  ("point", FilenameCase::Point),
+  ("SCREAMING_SNAKE_CASE", FilenameCase::ScreamingSnake),
+  ("screaming_snake", FilenameCase::ScreamingSnake),
+  ("SCREAMING_SNAKE", FilenameCase::ScreamingSnake),
+];
+
+/// Parses the canonical spellings printed by `Display`, plus short aliases such as `kebab`,
+/// `snake`, `camel`, `pascal` and `screaming_snake`.
 impl FromStr for FilenameCase {
   type Err = String;
   fn from_str(s: &str) -> anyhow::Result<Self, Self::Err> {
-    match s {
-      "lowercase" => Ok(FilenameCase::Lower),
-      "snake_case" => Ok(FilenameCase::Snake),
-      "camelCase" => Ok(FilenameCase::Camel),
-      "kebab-case" => Ok(FilenameCase::Kebab),
-      "Pascal" => Ok(FilenameCase::Pascal),
-      "point.case" => Ok(FilenameCase::Point),
-      "SCREAMING_SNAKE_CASE" => Ok(FilenameCase::ScreamingSnake),
-      _ => Err(format!("Unknown filename case: {}", s)),
+    match SPELLINGS.iter().find(|(spelling, _)| *spelling == s) {
+      Some((_, case)) => Ok(*case),
+      // Debug formatting quotes and escapes the value, so invisible characters show up.
+      None => Err(format!("Unknown filename case: {:?}", s)),
     }
   }
 }
@@ -50,47 +89,185 @@ impl Display for FilenameCase {
   }
 }
 
+/// Toggles selecting a relaxed variant of the camelCase and PascalCase patterns.
+#[derive(Debug, Default, PartialEq, Copy, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CaseOptions {
+  /// Accept a leading run of capitals as one word: `URLParser`, `HTMLParser`, `3DModel`.
+  #[serde(default)]
+  pub allow_acronyms: bool,
+  /// Accept digits in front of the first word: `2dPoint`, `2FactorAuth`.
+  #[serde(default)]
+  pub allow_leading_digit: bool,
+  /// Accept digits at the end of the first camelCase word: `http2Server`. Later words, and
+  /// every PascalCase word, accept trailing digits regardless.
+  #[serde(default)]
+  pub allow_trailing_digit: bool,
+  /// Reject words of a single capital after the first word, as in `aBTest`, unless capitals run
+  /// on as an acronym: `parseHTMLParser` and `iOSApp` pass, as does a trailing `parseHTML`. By
+  /// default every capital may start a word of its own.
+  #[serde(default)]
+  pub strict_capitals: bool,
+}
+
+impl CaseOptions {
+  const VARIANTS: usize = 16;
+
+  fn from_variant(variant: usize) -> Self {
+    CaseOptions {
+      allow_acronyms: variant & 1 != 0,
+      allow_leading_digit: variant & 2 != 0,
+      allow_trailing_digit: variant & 4 != 0,
+      strict_capitals: variant & 8 != 0,
+    }
+  }
+
+  fn variant(&self) -> usize {
+    self.allow_acronyms as usize
+      | (self.allow_leading_digit as usize) << 1
+      | (self.allow_trailing_digit as usize) << 2
+      | (self.strict_capitals as usize) << 3
+  }
+
+  /// The words after the first one of a camelCase or PascalCase stem.
+  fn later_words(&self) -> &'static str {
+    match self.strict_capitals {
+      true => "(([A-Z]{2,})?[A-Z][a-z0-9]+)*([A-Z]{2,})?",
+      false => "([A-Z][a-z0-9]*)*",
+    }
+  }
+}
+
+fn camel_case_pattern(options: &CaseOptions) -> String {
+  let lead = if options.allow_leading_digit { "[0-9]*" } else { "" };
+  let word = if options.allow_trailing_digit { "[a-z]+[0-9]*" } else { "[a-z]+" };
+  let first = if options.allow_acronyms { format!("({}|[A-Z]{{2,}})", word) } else { word.into() };
+  format!("^{}{}{}$", lead, first, options.later_words())
+}
+
+fn pascal_case_pattern(options: &CaseOptions) -> String {
+  let lead = if options.allow_leading_digit { "[0-9]*" } else { "" };
+  let first = if options.allow_acronyms { "([A-Z][a-z0-9]+|[A-Z]+)" } else { "[A-Z][a-z0-9]+" };
+  format!("^{}{}{}$", lead, first, options.later_words())
+}
+
+fn case_variants(pattern: fn(&CaseOptions) -> String) -> Vec<Regex> {
+  (0..CaseOptions::VARIANTS)
+    .map(|variant| Regex::new(&pattern(&CaseOptions::from_variant(variant))).unwrap())
+    .collect()
+}
+
 struct FilenamePatterns {
   snake_case: LazyLock<Regex>,
-  camel_case: LazyLock<Regex>,
+  camel_case: LazyLock<Vec<Regex>>,
   kebab_case: LazyLock<Regex>,
-  pascal_case: LazyLock<Regex>,
+  pascal_case: LazyLock<Vec<Regex>>,
   lower_case: LazyLock<Regex>,
   point_case: LazyLock<Regex>,
   screaming_snake_case: LazyLock<Regex>,
   none_split: LazyLock<Regex>, // No any `.`, `_`, capital letter
 }
 
+const SNAKE_CASE: &str = r"^[a-z0-9_]+$";
+const KEBAB_CASE: &str = r"^[a-z0-9]+(-[a-z0-9]+)*$";
+const LOWER_CASE: &str = r"^[a-z0-9]+$";
+const POINT_CASE: &str = r"^[a-z0-9]+(\.[a-z0-9]+)*$";
+const SCREAMING_SNAKE_CASE: &str = r"^[A-Z0-9_]+$";
+pub(crate) const NONE_SPLIT: &str = r"^[a-z0-9]+$";
+
 static PATTERNS: FilenamePatterns = FilenamePatterns {
-  snake_case: LazyLock::new(|| Regex::new(r"^[a-z0-9_]+$").unwrap()),
-  camel_case: LazyLock::new(|| Regex::new(r"^[a-z]+([A-Z][a-z0-9]*)*$").unwrap()),
-  kebab_case: LazyLock::new(|| Regex::new(r"^[a-z0-9]+(-[a-z0-9]+)*$").unwrap()),
-  pascal_case: LazyLock::new(|| Regex::new(r"^[A-Z][a-z0-9]+([A-Z][a-z0-9]*)*$").unwrap()),
-  lower_case: LazyLock::new(|| Regex::new(r"^[a-z0-9]+$").unwrap()),
-  point_case: LazyLock::new(|| Regex::new(r"^[a-z0-9]+(\.[a-z0-9]+)*$").unwrap()),
-  screaming_snake_case: LazyLock::new(|| Regex::new(r"^[A-Z0-9_]+$").unwrap()),
-  none_split: LazyLock::new(|| Regex::new(r"^[a-z0-9]+$").unwrap()),
+  snake_case: LazyLock::new(|| Regex::new(SNAKE_CASE).unwrap()),
+  camel_case: LazyLock::new(|| case_variants(camel_case_pattern)),
+  kebab_case: LazyLock::new(|| Regex::new(KEBAB_CASE).unwrap()),
+  pascal_case: LazyLock::new(|| case_variants(pascal_case_pattern)),
+  lower_case: LazyLock::new(|| Regex::new(LOWER_CASE).unwrap()),
+  point_case: LazyLock::new(|| Regex::new(POINT_CASE).unwrap()),
+  screaming_snake_case: LazyLock::new(|| Regex::new(SCREAMING_SNAKE_CASE).unwrap()),
+  none_split: LazyLock::new(|| Regex::new(NONE_SPLIT).unwrap()),
 };
 
+/// Whether `stem` is one lowercase word, without separators or capitals.
+pub(crate) fn is_single_word(stem: &str) -> bool {
+  PATTERNS.none_split.is_match(stem)
+}
+
 impl FilenameCase {
-  pub(crate) fn matches(&self, filename: &str) -> bool {
-    if PATTERNS.none_split.is_match(filename) {
+  /// Every case, from the most restrictive to the least. Many stems fit several cases (`hello`
+  /// is lowercase, snake_case, kebab-case, point.case and camelCase at once), so whenever one
+  /// case has to be picked for a stem, the first in this order wins: `Lower` before the
+  /// separated cases, which come before `Camel`, `Pascal` and `ScreamingSnake`.
+  pub const ALL: [FilenameCase; 7] = [
+    FilenameCase::Lower,
+    FilenameCase::Snake,
+    FilenameCase::Kebab,
+    FilenameCase::Point,
+    FilenameCase::Camel,
+    FilenameCase::Pascal,
+    FilenameCase::ScreamingSnake,
+  ];
+
+  /// The case a stem is written in, or `None` when it fits no case at all. A single lowercase
+  /// word such as `utils` is reported as `Lower`.
+  pub fn detect(stem: &str) -> Option<FilenameCase> {
+    Self::detect_all(stem).first().copied()
+  }
+
+  /// Every case the stem fits, in the precedence order of [`FilenameCase::ALL`].
+  pub fn detect_all(stem: &str) -> Vec<FilenameCase> {
+    let options = CaseOptions::default();
+    Self::ALL.into_iter().filter(|case| case.matches_pattern(stem, &options)).collect()
+  }
+
+  /// The anchored regular expression this case is checked with.
+  pub(crate) fn pattern(&self, options: &CaseOptions) -> String {
+    match self {
+      FilenameCase::Snake => SNAKE_CASE.to_string(),
+      FilenameCase::Camel => camel_case_pattern(options),
+      FilenameCase::Kebab => KEBAB_CASE.to_string(),
+      FilenameCase::Pascal => pascal_case_pattern(options),
+      FilenameCase::Lower => LOWER_CASE.to_string(),
+      FilenameCase::Point => POINT_CASE.to_string(),
+      FilenameCase::ScreamingSnake => SCREAMING_SNAKE_CASE.to_string(),
+    }
+  }
+
+  /// Whether `filename` is written in this case. A single lowercase word such as `utils` fits
+  /// every case; rules can opt out with `allowSingleWord`.
+  pub fn matches(&self, filename: &str, options: &CaseOptions) -> bool {
+    if is_single_word(filename) {
       return true;
     }
-    match self {
-      FilenameCase::Lower
-      | FilenameCase::Point
-      | FilenameCase::Snake
-      | FilenameCase::Kebab
-      | FilenameCase::Camel
-        if PATTERNS.none_split.is_match(filename) =>
-      {
-        true
+    self.matches_pattern(filename, options)
+  }
+
+  /// Like [`matches`](FilenameCase::matches), for a whole filename: both the stem, up to the
+  /// last dot, and the extension must match; see [`extension_matches`](Self::extension_matches).
+  /// A dotfile such as `.eslintrc` has no extension and is matched as a whole.
+  pub fn matches_full(&self, filename: &str, options: &CaseOptions) -> bool {
+    match filename.rfind('.').filter(|dot| *dot > 0) {
+      Some(dot) => {
+        self.matches(&filename[..dot], options) && self.extension_matches(&filename[dot..])
       }
+      None => self.matches(filename, options),
+    }
+  }
+
+  /// Whether every dot-separated segment of `ext`, e.g. `.test.ts`, is lowercase letters and
+  /// digits. `ScreamingSnake` names may use an uppercase extension instead, as in `README.MD`.
+  pub fn extension_matches(&self, ext: &str) -> bool {
+    ext.split('.').filter(|segment| !segment.is_empty()).all(|segment| {
+      let lower = segment.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit());
+      let upper = segment.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+      lower || (*self == FilenameCase::ScreamingSnake && upper)
+    })
+  }
+
+  fn matches_pattern(&self, filename: &str, options: &CaseOptions) -> bool {
+    match self {
       FilenameCase::Snake => PATTERNS.snake_case.is_match(filename),
-      FilenameCase::Camel => PATTERNS.camel_case.is_match(filename),
+      FilenameCase::Camel => PATTERNS.camel_case[options.variant()].is_match(filename),
       FilenameCase::Kebab => PATTERNS.kebab_case.is_match(filename),
-      FilenameCase::Pascal => PATTERNS.pascal_case.is_match(filename),
+      FilenameCase::Pascal => PATTERNS.pascal_case[options.variant()].is_match(filename),
       FilenameCase::Lower => PATTERNS.lower_case.is_match(filename),
       FilenameCase::Point => PATTERNS.point_case.is_match(filename),
       FilenameCase::ScreamingSnake => PATTERNS.screaming_snake_case.is_match(filename),
@@ -98,38 +275,447 @@ impl FilenameCase {
   }
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+/// How serious an issue is, for formats that carry it.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Copy, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+  #[default]
+  Error,
+  Warning,
+  Info,
+}
+
+impl Display for Severity {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Severity::Error => write!(f, "error"),
+      Severity::Warning => write!(f, "warning"),
+      Severity::Info => write!(f, "info"),
+    }
+  }
+}
+
+/// Sample names an `ls` rule must accept and reject, checked by `--config-test`.
+#[derive(Debug, Default, Deserialize, PartialEq, JsonSchema)]
+pub struct ConfigTest {
+  #[serde(default)]
+  pub valid: Vec<String>,
+  #[serde(default)]
+  pub invalid: Vec<String>,
+}
+
+/// What happens to a file that no `ls` rule applies to.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum UnmatchedFiles {
+  /// Skip it, as if it were not scanned.
+  #[default]
+  Ignore,
+  /// Report it with the `warning` severity.
+  Warn,
+  /// Report it with the `error` severity.
+  Error,
+}
+
+/// How the paths of issues are printed.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PathStyle {
+  /// Relative to the scan root, or to `--relative-to`, e.g. `src/main.rs`.
+  #[default]
+  Relative,
+  /// Absolute, e.g. `/home/me/project/src/main.rs`.
+  Absolute,
+  /// A `file://` URI of the absolute path, for tools that link to files.
+  Uri,
+}
+
+impl FromStr for PathStyle {
+  type Err = String;
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "relative" => Ok(PathStyle::Relative),
+      "absolute" => Ok(PathStyle::Absolute),
+      "uri" => Ok(PathStyle::Uri),
+      _ => Err(format!("Unknown path style: {:?}, expected relative, absolute or uri", s)),
+    }
+  }
+}
+
+/// A single `ls` entry: the cases a file may use, plus per-rule options.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilenameRule {
+  pub cases: Arc<Vec<FilenameCase>>,
+  /// Whether a stem made only of digits (e.g. `404`) is accepted regardless of case.
+  pub allow_numeric_only: bool,
+  /// Whether a stem of one lowercase word, such as `utils`, passes whatever the cases. When
+  /// off, such a stem only passes a rule listing `lowercase`, so `snake_case` asks for an
+  /// underscore and `kebab-case` for a dash.
+  pub allow_single_word: bool,
+  /// Whether the stem may contain dots. When off, `a.b.json` fails even under `point.case`,
+  /// so a dot only ever appears as the extension separator.
+  pub allow_dots_in_stem: bool,
+  /// Build qualifiers such as `min` or `prod` that may follow the stem as one last dotted
+  /// segment, e.g. `styles.min.css`. Such a qualifier is set aside before the case check.
+  pub allow_qualifiers: Vec<String>,
+  /// Whether the stem may end in a locale as one last dotted segment, e.g. `messages.en-US.json`
+  /// or `messages.zh-Hans.json`. The locale is set aside before the case check, and a last
+  /// segment that is no locale, such as `english`, is reported. A stem without a dot passes on.
+  pub locale_suffix: bool,
+  pub case_options: CaseOptions,
+  /// Replaces the default issue text; `{filename}`, `{cases}` and `{suggestion}` are filled in.
+  pub message: Option<Arc<str>>,
+  /// Also check every directory a matching file sits in against the rule's cases.
+  pub full_path: bool,
+  /// With `full_path`, accept all-uppercase directory names up to this length, e.g. `3` allows
+  /// `API/` and `UI/`. `0` disables the exception.
+  pub allow_acronym_dirs: usize,
+  /// With `full_path`, leave this many leading directories unchecked, e.g. `1` for the package
+  /// directories of a monorepo, which follow a convention of their own.
+  pub skip_dirs: usize,
+  /// Checked in order before the rule's own cases; the first match decides the cases.
+  pub exceptions: Vec<CaseException>,
+  /// Patterns, written like the top-level `ignore`, of files this rule leaves alone, e.g.
+  /// `__generated__/`. Unlike the top-level `ignore`, such files are still scanned, so the
+  /// other checks and rules see them.
+  pub ignore: Vec<String>,
+  /// Report files without an extension instead of checking their case. Only useful on a glob
+  /// key naming no extension, such as `src/**`, since an extension key never matches such a
+  /// file. Dotfiles like `.eslintrc` count as having no extension, and `exceptions` do not
+  /// exempt a file.
+  pub require_extension: bool,
+  /// Also hold the extension to the case, see [`FilenameCase::matches_full`], so `page.TSX`
+  /// fails a kebab-case rule. Only useful on a glob key, since an extension key already fixes
+  /// the extension.
+  pub include_extension: bool,
+  pub severity: Severity,
+}
+
+/// Stems matching `pattern`, e.g. `^use[A-Z]` for React hooks, are held to `cases` instead of
+/// the cases of the rule they belong to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaseException {
+  pub pattern: UserRegex,
+  pub cases: Arc<Vec<FilenameCase>>,
+}
+
+impl From<Vec<FilenameCase>> for FilenameRule {
+  fn from(cases: Vec<FilenameCase>) -> Self {
+    FilenameRule {
+      cases: Arc::new(cases),
+      allow_numeric_only: true,
+      allow_single_word: true,
+      allow_dots_in_stem: true,
+      allow_qualifiers: vec![],
+      locale_suffix: false,
+      case_options: CaseOptions::default(),
+      message: None,
+      full_path: false,
+      allow_acronym_dirs: 0,
+      skip_dirs: 0,
+      exceptions: vec![],
+      ignore: vec![],
+      require_extension: false,
+      include_extension: false,
+      severity: Severity::Error,
+    }
+  }
+}
+
+/// The raw shape of an `ls` value: a single case, a plain list of cases, or an object
+/// carrying the cases together with rule options.
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum RawRule {
+  Case(#[schemars(with = "FilenameCase")] String),
+  Cases(#[schemars(with = "Vec<FilenameCase>")] Vec<String>),
+  Detailed(DetailedRule),
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct DetailedRule {
+  #[schemars(with = "Vec<FilenameCase>")]
+  cases: Vec<String>,
+  #[serde(default = "default_true")]
+  allow_numeric_only: bool,
+  #[serde(default = "default_true")]
+  allow_single_word: bool,
+  #[serde(default = "default_true")]
+  allow_dots_in_stem: bool,
+  #[serde(default)]
+  allow_qualifiers: Vec<String>,
+  #[serde(default)]
+  locale_suffix: bool,
+  #[serde(flatten)]
+  case_options: CaseOptions,
+  #[serde(default)]
+  message: Option<String>,
+  #[serde(default)]
+  full_path: bool,
+  #[serde(default)]
+  allow_acronym_dirs: usize,
+  #[serde(default)]
+  skip_dirs: usize,
+  #[serde(default)]
+  exceptions: Vec<RawException>,
+  #[serde(default)]
+  ignore: Vec<String>,
+  #[serde(default)]
+  require_extension: bool,
+  #[serde(default)]
+  include_extension: bool,
+  #[serde(default)]
+  severity: Severity,
+}
+
+impl DetailedRule {
+  /// The rule a bare case or list of cases stands for, with every option at its default.
+  fn from_cases(cases: Vec<String>) -> Self {
+    DetailedRule {
+      cases,
+      allow_numeric_only: true,
+      allow_single_word: true,
+      allow_dots_in_stem: true,
+      allow_qualifiers: vec![],
+      locale_suffix: false,
+      case_options: CaseOptions::default(),
+      message: None,
+      full_path: false,
+      allow_acronym_dirs: 0,
+      skip_dirs: 0,
+      exceptions: vec![],
+      ignore: vec![],
+      require_extension: false,
+      include_extension: false,
+      severity: Severity::Error,
+    }
+  }
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct RawException {
+  pattern: UserRegex,
+  #[schemars(with = "Vec<FilenameCase>")]
+  cases: Vec<String>,
+}
+
+fn parse_cases<E: de::Error>(cases: Vec<String>) -> Result<Vec<FilenameCase>, E> {
+  cases.into_iter().map(|s| FilenameCase::from_str(s.trim()).map_err(de::Error::custom)).collect()
+}
+
+fn default_true() -> bool {
+  true
+}
+
+/// A regular expression supplied by the config, compiled when the config is loaded.
+///
+/// The `regex` crate matches in time linear in the input, so a pattern cannot backtrack
+/// catastrophically. What a pattern can do is compile into a huge program, e.g. `(a|b){50000}`;
+/// patterns whose compiled size exceeds the `regexSizeLimit` of the config (in bytes, 1 MiB
+/// by default) are rejected at load time.
+#[derive(Debug, Clone)]
+pub struct UserRegex(pub Regex);
+
+/// The default of `regexSizeLimit`.
+pub const DEFAULT_REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+thread_local! {
+  /// The size limit applied while a config is deserialized on this thread.
+  static REGEX_SIZE_LIMIT: Cell<usize> = const { Cell::new(DEFAULT_REGEX_SIZE_LIMIT) };
+}
+
+impl PartialEq for UserRegex {
+  fn eq(&self, other: &Self) -> bool {
+    self.0.as_str() == other.0.as_str()
+  }
+}
+
+impl<'de> Deserialize<'de> for UserRegex {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    let pattern = String::deserialize(deserializer)?;
+    RegexBuilder::new(&pattern)
+      .size_limit(REGEX_SIZE_LIMIT.get())
+      .build()
+      .map(UserRegex)
+      .map_err(de::Error::custom)
+  }
+}
+
+/// Opt-in check that every test file sits next to a source file of the same name.
+#[derive(Debug, Deserialize, PartialEq, JsonSchema)]
+pub struct TestFileConfig {
+  /// Stem suffixes marking a test file, e.g. `.test` for `userService.test.ts`.
+  pub suffixes: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct FilenameLintConfig {
+  /// Rules keyed by file extension, whose leading dot is optional, by the name of a category, or
+  /// by a path glob such as `src/components/**/*.ts`. The most specific glob matching a file
+  /// decides its rule, the first declared one among equally specific globs, and extension rules
+  /// only apply to files no glob matches. Rules keep the order they are written in.
   #[serde(deserialize_with = "deserialize_map")]
-  pub ls: HashMap<String, Vec<FilenameCase>>,
+  #[schemars(with = "IndexMap<String, RawRule>")]
+  pub ls: IndexMap<String, FilenameRule>,
+  /// Marks the config as a discovery boundary, like `root: true` in ESLint. The nearest config
+  /// is always used alone, without merging in those of parent directories, so this is implied;
+  /// a [`ROOT_MARKER`] file draws the same boundary for a directory without a config.
+  #[serde(default)]
+  pub root: bool,
+  /// Named groups of extensions, e.g. `style` for `.css`, `.scss` and `.less`, so a single `ls`
+  /// rule keyed by the category covers all of them.
+  #[serde(default)]
+  pub categories: HashMap<String, Vec<String>>,
   pub ignore: Vec<String>,
+  /// Globs restricting the scan; when empty, every file that isn't ignored is scanned.
+  #[serde(default)]
+  pub include: Vec<String>,
+  #[serde(default)]
+  pub test_files: Option<TestFileConfig>,
+  /// Files every directory matching a glob must contain, e.g. `{ "src/components/*": ["index.ts"] }`.
+  #[serde(default)]
+  pub required_files: HashMap<String, Vec<String>>,
+  /// Report files named like a directory next to them, e.g. `utils.ts` beside `utils/`.
+  #[serde(default)]
+  pub no_shadowed_dirs: bool,
+  /// Files whose first bytes match this pattern, e.g. `@generated`, are not linted.
+  #[serde(default)]
+  pub generated_marker: Option<UserRegex>,
+  /// The largest compiled size, in bytes, of a regex in this config; see [`UserRegex`].
+  #[serde(default)]
+  pub regex_size_limit: Option<usize>,
+  /// Lowercase the extension too when `--fix` renames a file, e.g. `MyComponent.JS` to
+  /// `my-component.js`. By default the extension is kept as is.
+  #[serde(default)]
+  pub normalize_extension_case: bool,
+  /// How issue paths are printed, whatever the format; `--path-style` overrides it.
+  #[serde(default)]
+  pub path_style: PathStyle,
+  /// Acronyms such as `GRPC` that suggestions and `--fix` treat as one word, so `GRPCClient`
+  /// becomes `grpc_client` rather than `g_r_p_c_client`.
+  #[serde(default)]
+  pub known_acronyms: Vec<String>,
+  /// Every extension a scanned file may have, e.g. `[".ts", ".rs"]` to catch a stray `.orig`.
+  /// Only the last extension counts, and files without one pass. Empty allows any.
+  #[serde(default)]
+  pub allowed_extensions: Vec<String>,
+  /// Whether files no `ls` rule applies to are reported, so none goes unchecked unnoticed.
+  /// Either way such files still take part in `testFiles` and the other whole-tree checks.
+  #[serde(default)]
+  pub unmatched_files: UnmatchedFiles,
+  /// Report relative imports of JS and TS files that name a file in another case than its own,
+  /// such as `./fooBar` for `FooBar.ts`, which breaks on case-sensitive filesystems. Needs the
+  /// `imports` feature, and is ignored without it.
+  #[serde(default)]
+  pub import_case: bool,
+  /// Report words of filenames missing from a wordlist. Off unless set.
+  #[serde(default)]
+  pub spelling: Option<SpellingConfig>,
+  /// Sample names per `ls` key, e.g. `{ ".ts": { "valid": ["fooBar"], "invalid": ["FooBar"] } }`,
+  /// that `--config-test` checks the rules against.
+  #[serde(default)]
+  pub tests: IndexMap<String, ConfigTest>,
+  /// Walk into symlinked directories too. Links are linted by their own name either way, and
+  /// a link to a file is always scanned.
+  #[serde(default)]
+  pub follow_symlinks: bool,
+  /// With `followSymlinks`, warn about each link whose target lies outside the scan root.
+  #[serde(default = "default_true")]
+  pub warn_external_symlinks: bool,
+  /// Subtrees linted with rules of their own. Files outside every root keep the rules above.
+  #[serde(default)]
+  pub roots: Vec<Root>,
+}
+
+/// Words a filename may be made of, to catch typos such as `recieve.md` in user-facing content.
+#[derive(Debug, Deserialize, PartialEq, JsonSchema)]
+pub struct SpellingConfig {
+  /// A file of known words, one per line, relative to the config file declaring it. Case is
+  /// ignored, as are blank lines and `#` comments.
+  pub wordlist: PathBuf,
+  /// Globs of the files to check, e.g. `docs/**/*.md`; when empty, every file is checked.
+  #[serde(default)]
+  pub files: Vec<String>,
 }
 
-fn deserialize_map<'de, D>(deserializer: D) -> Result<HashMap<String, Vec<FilenameCase>>, D::Error>
+/// `ext` with a leading dot, e.g. `.rs` for `rs`.
+pub(crate) fn dotted(ext: String) -> String {
+  match ext.starts_with('.') || ext.is_empty() {
+    true => ext,
+    false => format!(".{}", ext),
+  }
+}
+
+/// A subtree of the scan, such as one package of a monorepo, with its own config.
+#[derive(Debug, Deserialize, PartialEq, JsonSchema)]
+pub struct Root {
+  /// The subtree, relative to the scan root, e.g. `packages/web`. When roots nest, the longest
+  /// path containing a file wins.
+  pub path: String,
+  /// Given inline, or as the path of a config file relative to the declaring one. Globs and
+  /// `ignore` patterns in it are matched against paths relative to the root; `ignore` may be
+  /// left out.
+  #[schemars(with = "schema::RootConfig")]
+  pub config: FilenameLintConfig,
+}
+
+fn deserialize_map<'de, D>(deserializer: D) -> Result<IndexMap<String, FilenameRule>, D::Error>
 where
   D: Deserializer<'de>,
 {
   struct MapVisitor;
 
   impl<'de> Visitor<'de> for MapVisitor {
-    type Value = HashMap<String, Vec<FilenameCase>>;
+    type Value = IndexMap<String, FilenameRule>;
 
     fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-      formatter.write_str("a map of strings to lists of filename cases")
+      formatter.write_str("a map of strings to filename cases, lists of them or rule objects")
     }
 
     fn visit_map<M>(self, mut access: M) -> Result<Self::Value, M::Error>
     where
       M: de::MapAccess<'de>,
     {
-      let mut map = HashMap::new();
+      let mut map = IndexMap::new();
 
-      while let Some((key, value)) = access.next_entry::<String, Vec<String>>()? {
-        let cases: Vec<FilenameCase> = value
+      while let Some((key, value)) = access.next_entry::<String, RawRule>()? {
+        let value = match value {
+          RawRule::Case(case) => DetailedRule::from_cases(vec![case]),
+          RawRule::Cases(cases) => DetailedRule::from_cases(cases),
+          RawRule::Detailed(rule) => rule,
+        };
+        let cases = parse_cases(value.cases)?;
+        let exceptions = value
+          .exceptions
           .into_iter()
-          .map(|s| FilenameCase::from_str(&s).map_err(de::Error::custom))
-          .collect::<Result<_, _>>()?;
-        map.insert(key, cases);
+          .map(|exception| {
+            let cases = Arc::new(parse_cases(exception.cases)?);
+            Ok(CaseException { pattern: exception.pattern, cases })
+          })
+          .collect::<Result<_, M::Error>>()?;
+        map.insert(
+          key,
+          FilenameRule {
+            cases: Arc::new(cases),
+            allow_numeric_only: value.allow_numeric_only,
+            allow_single_word: value.allow_single_word,
+            allow_dots_in_stem: value.allow_dots_in_stem,
+            allow_qualifiers: value.allow_qualifiers,
+            locale_suffix: value.locale_suffix,
+            case_options: value.case_options,
+            message: value.message.map(Arc::from),
+            full_path: value.full_path,
+            allow_acronym_dirs: value.allow_acronym_dirs,
+            skip_dirs: value.skip_dirs,
+            exceptions,
+            ignore: value.ignore,
+            require_extension: value.require_extension,
+            include_extension: value.include_extension,
+            severity: value.severity,
+          },
+        );
       }
 
       Ok(map)
@@ -140,41 +726,564 @@ where
 }
 
 impl FilenameLintConfig {
-  pub fn load_file() -> Result<Self> {
-    let json_path = Path::new("./fnlint.config.json");
-    if json_path.exists() {
-      Self::load_json(json_path.to_str().unwrap().to_string())
-    } else {
-      let yaml_path = Path::new("./fnlint.config.yaml");
-      if yaml_path.exists() {
-        Self::load_yaml(yaml_path.to_str().unwrap().to_string())
-      } else {
-        let toml_path = Path::new("./fnlint.config.toml");
-        if toml_path.exists() {
-          Self::load_toml(toml_path.to_str().unwrap().to_string())
-        } else {
-          panic!("No configuration file found");
+  pub fn find_file() -> Option<PathBuf> {
+    Self::find_file_from(Path::new("."))
+  }
+
+  /// Look for a config file in `dir`, then in each of its parents, so a package inside a
+  /// monorepo shares the root config. The search stops at the directory holding `.git` or a
+  /// [`ROOT_MARKER`], or at the filesystem root.
+  ///
+  /// The config found stands alone: those of parent directories are never merged into it, only
+  /// what it names under `extends`. A `root: true` config is thus a boundary already.
+  pub fn find_file_from(dir: &Path) -> Option<PathBuf> {
+    let dir = absolute(dir).ok()?;
+    for ancestor in dir.ancestors() {
+      let found = CONFIG_FILES.iter().map(|name| ancestor.join(name)).find(|path| path.exists());
+      let boundary = ancestor.join(".git").exists() || ancestor.join(ROOT_MARKER).exists();
+      if found.is_some() || boundary {
+        return found;
+      }
+    }
+    None
+  }
+
+  /// Load the config at `path`, following `extends`, and report which source set which keys.
+  pub fn load_traced(path: &Path) -> Result<(Self, ConfigTrace)> {
+    let (mut config, mut trace) = resolve(path, Self::load_value)?;
+    Self::resolve_wordlist(&mut config, path);
+    Self::inline_roots(&mut config, path, &mut trace, &mut vec![])?;
+    let limit = config.get("regexSizeLimit").and_then(Value::as_u64).map(|limit| limit as usize);
+    REGEX_SIZE_LIMIT.set(limit.unwrap_or(DEFAULT_REGEX_SIZE_LIMIT));
+    let config = serde_json::from_value::<Self>(config);
+    REGEX_SIZE_LIMIT.set(DEFAULT_REGEX_SIZE_LIMIT);
+    let mut config = config?;
+    config.normalize_extensions()?;
+    config.check_globs()?;
+    Ok((config, trace))
+  }
+
+  /// Whether any `ls` rule is defined, here or in a root; without one no file is checked.
+  pub fn has_rules(&self) -> bool {
+    !self.ls.is_empty() || self.roots.iter().any(|root| root.config.has_rules())
+  }
+
+  /// Hold the files of every `ls` rule, those of `roots` included, to `case` alone. Exceptions
+  /// are dropped, as they name cases of their own; which files a rule covers and its other
+  /// options stay, so files no rule covers are still left alone.
+  pub fn enforce(&mut self, case: FilenameCase) {
+    let cases = Arc::new(vec![case]);
+    for rule in self.ls.values_mut() {
+      rule.cases = cases.clone();
+      rule.exceptions.clear();
+    }
+    self.roots.iter_mut().for_each(|root| root.config.enforce(case));
+  }
+
+  /// Give extension keys, category members and allowed extensions without a leading dot one,
+  /// so `rs` and `.rs` both mean the `.rs` extension. Globs and category names are left alone.
+  /// Two keys spelling the same extension, such as `ts` and `.ts`, are an error rather than one
+  /// silently replacing the other.
+  pub fn normalize_extensions(&mut self) -> Result<()> {
+    let categories = &self.categories;
+    let key = |key: &String| match is_glob(key) || categories.contains_key(key) {
+      true => key.clone(),
+      false => dotted(key.clone()),
+    };
+    fn rekey<T>(
+      map: IndexMap<String, T>,
+      section: &str,
+      key: impl Fn(&String) -> String,
+    ) -> Result<IndexMap<String, T>> {
+      let mut spellings = HashMap::<String, String>::new();
+      let mut rekeyed = IndexMap::new();
+      for (spelling, value) in map {
+        let normalized = key(&spelling);
+        if let Some(first) = spellings.insert(normalized.clone(), spelling.clone()) {
+          bail!(
+            "Duplicate key in `{}`: `{}` and `{}` both name `{}`",
+            section,
+            first,
+            spelling,
+            normalized
+          );
         }
+        rekeyed.insert(normalized, value);
       }
+      Ok(rekeyed)
+    }
+    self.ls = rekey(std::mem::take(&mut self.ls), "ls", key)?;
+    self.tests = rekey(std::mem::take(&mut self.tests), "tests", key)?;
+    for exts in self.categories.values_mut() {
+      *exts = std::mem::take(exts).into_iter().map(dotted).collect();
     }
+    self.allowed_extensions =
+      std::mem::take(&mut self.allowed_extensions).into_iter().map(dotted).collect();
+    for root in &mut self.roots {
+      root.config.normalize_extensions()?;
+    }
+    Ok(())
   }
 
-  fn load_json(path: String) -> Result<Self> {
-    let config = std::fs::read_to_string(path)?;
+  /// Reject globs that do not compile, such as `src/{a,b`, in `ls` keys and pattern lists, so
+  /// the lint never meets one. Loading a config does this already.
+  pub fn check_globs(&self) -> Result<()> {
+    let ls = self.ls.keys().filter(|key| is_glob(key));
+    let rule_ignore = self.ls.values().flat_map(|rule| &rule.ignore);
+    let required = self.required_files.keys();
+    let spelling = self.spelling.iter().flat_map(|spelling| &spelling.files);
+    let globs = ls.chain(rule_ignore).chain(&self.ignore).chain(&self.include);
+    for glob in globs.chain(required).chain(spelling) {
+      Pattern::new(glob.trim_end_matches('/'))?;
+    }
+    self.roots.iter().try_for_each(|root| root.config.check_globs())
+  }
+
+  /// Replace each root config given as a path with the content of that file, recursively.
+  fn inline_roots(
+    config: &mut Value,
+    path: &Path,
+    trace: &mut ConfigTrace,
+    loading: &mut Vec<PathBuf>,
+  ) -> Result<()> {
+    let Some(Value::Array(roots)) = config.get_mut("roots") else {
+      return Ok(());
+    };
+    loading.push(path.canonicalize()?);
+    for root in roots {
+      let Some(config) = root.get_mut("config") else {
+        continue;
+      };
+      match config {
+        Value::String(file) => {
+          let file = path.parent().unwrap_or(Path::new(".")).join(&*file);
+          if loading.contains(&file.canonicalize().unwrap_or_else(|_| file.clone())) {
+            bail!("Circular `roots` in {}", file.display());
+          }
+          let (mut loaded, loaded_trace) = resolve(&file, Self::load_value)?;
+          trace.sources.extend(loaded_trace.sources);
+          Self::resolve_wordlist(&mut loaded, &file);
+          Self::inline_roots(&mut loaded, &file, trace, loading)?;
+          *config = loaded;
+        }
+        _ => {
+          Self::resolve_wordlist(config, path);
+          Self::inline_roots(config, path, trace, loading)?
+        }
+      }
+      if let Value::Object(object) = config {
+        object.entry("ignore").or_insert(Value::Array(vec![]));
+      }
+    }
+    loading.pop();
+    Ok(())
+  }
+
+  /// Make the `spelling` wordlist of the config loaded from `path` relative to the working
+  /// directory rather than to that file.
+  fn resolve_wordlist(config: &mut Value, path: &Path) {
+    let Some(Value::String(wordlist)) = config.pointer_mut("/spelling/wordlist") else {
+      return;
+    };
+    let parent = path.parent().unwrap_or(Path::new("."));
+    *wordlist = parent.join(&*wordlist).to_string_lossy().into_owned();
+  }
+
+  fn load_value(path: &Path) -> Result<Value> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+      Some("json") => Self::load_json(path),
+      Some("yaml") | Some("yml") => Self::load_yaml(path),
+      Some("toml") => Self::load_toml(path),
+      _ => bail!("Unsupported configuration format: {}", path.display()),
+    }
+  }
+
+  /// Read a config file as text. UTF-8 is expected; a UTF-8 or UTF-16 byte order mark, as some
+  /// Windows editors write, is honored.
+  fn read_config(path: &Path) -> Result<String> {
+    let bytes = std::fs::read(path)?;
+    let utf16 = |bytes: &[u8], decode: fn([u8; 2]) -> u16| {
+      let units = bytes.chunks_exact(2).map(|pair| decode([pair[0], pair[1]])).collect::<Vec<_>>();
+      String::from_utf16(&units).ok()
+    };
+    let text = match bytes.as_slice() {
+      [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8(rest.to_vec()).ok(),
+      [0xFF, 0xFE, rest @ ..] if rest.len() % 2 == 0 => utf16(rest, u16::from_le_bytes),
+      [0xFE, 0xFF, rest @ ..] if rest.len() % 2 == 0 => utf16(rest, u16::from_be_bytes),
+      _ => String::from_utf8(bytes).ok(),
+    };
+    match text {
+      Some(text) => Ok(text),
+      None => bail!("Cannot load {}: the file is not UTF-8 or UTF-16 with a BOM", path.display()),
+    }
+  }
+
+  fn load_json(path: &Path) -> Result<Value> {
+    let config = Self::read_config(path)?;
     let config: Value = serde_json::from_str(&config)?;
-    let config: Self = serde_json::from_value(config)?;
     Ok(config)
   }
 
-  fn load_yaml(path: String) -> Result<Self> {
-    let config = std::fs::read_to_string(path)?;
-    let config: Self = serde_yml::from_str(&config)?;
+  #[cfg(feature = "yaml")]
+  fn load_yaml(path: &Path) -> Result<Value> {
+    let config = Self::read_config(path)?;
+    let config: Value = serde_yml::from_str(&config)?;
     Ok(config)
   }
 
-  fn load_toml(path: String) -> Result<Self> {
-    let config = std::fs::read_to_string(path)?;
-    let config: Self = toml::from_str(&config)?;
+  #[cfg(not(feature = "yaml"))]
+  fn load_yaml(path: &Path) -> Result<Value> {
+    bail!("Cannot load {}: fnlint was built without the `yaml` feature", path.display())
+  }
+
+  #[cfg(feature = "toml")]
+  fn load_toml(path: &Path) -> Result<Value> {
+    let config = Self::read_config(path)?;
+    let config: Value = toml::from_str(&config)?;
     Ok(config)
   }
+
+  #[cfg(not(feature = "toml"))]
+  fn load_toml(path: &Path) -> Result<Value> {
+    bail!("Cannot load {}: fnlint was built without the `toml` feature", path.display())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn load_fixture(name: &str, content: &str) -> Result<FilenameLintConfig> {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(name);
+    std::fs::write(&path, content).unwrap();
+    FilenameLintConfig::load_traced(&path).map(|(config, _)| config)
+  }
+
+  #[test]
+  fn find_config_upward() {
+    let dir = tempfile::tempdir().unwrap();
+    let nested = dir.path().join("packages/web/src");
+    std::fs::create_dir_all(&nested).unwrap();
+    std::fs::create_dir(dir.path().join(".git")).unwrap();
+    assert_eq!(FilenameLintConfig::find_file_from(&nested), None);
+
+    let root = dir.path().join("fnlint.config.json");
+    std::fs::write(&root, "{}").unwrap();
+    assert_eq!(FilenameLintConfig::find_file_from(&nested), Some(root));
+    let package = dir.path().join("packages/web/fnlint.config.yaml");
+    std::fs::write(&package, "").unwrap();
+    assert_eq!(FilenameLintConfig::find_file_from(&nested), Some(package));
+
+    // a repository inside another does not pick up the outer config
+    std::fs::create_dir(dir.path().join("packages/web/src/.git")).unwrap();
+    assert_eq!(FilenameLintConfig::find_file_from(&nested), None);
+  }
+
+  #[test]
+  fn discovery_stops_at_root_markers() {
+    let dir = tempfile::tempdir().unwrap();
+    let nested = dir.path().join("packages/web/src");
+    std::fs::create_dir_all(&nested).unwrap();
+    std::fs::create_dir(dir.path().join(".git")).unwrap();
+    let ancestor = dir.path().join("fnlint.config.json");
+    std::fs::write(&ancestor, r#"{ "ls": { ".ts": "kebab-case" }, "ignore": ["dist"] }"#).unwrap();
+    assert_eq!(FilenameLintConfig::find_file_from(&nested), Some(ancestor));
+
+    std::fs::write(dir.path().join("packages/web").join(ROOT_MARKER), "").unwrap();
+    assert_eq!(FilenameLintConfig::find_file_from(&nested), None);
+
+    let child = dir.path().join("packages/web/fnlint.config.json");
+    let content = r#"{ "root": true, "ls": { ".rs": "snake_case" }, "ignore": [] }"#;
+    std::fs::write(&child, content).unwrap();
+    assert_eq!(FilenameLintConfig::find_file_from(&nested), Some(child.clone()));
+    // the nearest config does not inherit the rules of the one above it
+    let (config, trace) = FilenameLintConfig::load_traced(&child).unwrap();
+    assert!(config.root);
+    assert!(config.ignore.is_empty());
+    assert_eq!(config.ls.keys().collect::<Vec<_>>(), vec![".rs"]);
+    assert_eq!(trace.sources.len(), 1);
+  }
+
+  #[test]
+  fn load_roots() {
+    let dir = tempfile::tempdir().unwrap();
+    let web = dir.path().join("packages/web");
+    std::fs::create_dir_all(&web).unwrap();
+    std::fs::write(web.join("fnlint.config.json"), r#"{ "ls": { ".ts": ["kebab-case"] } }"#)
+      .unwrap();
+    let path = dir.path().join("fnlint.config.json");
+    std::fs::write(
+      &path,
+      r#"{ "ls": {}, "ignore": [], "roots": [
+        { "path": "packages/web", "config": "packages/web/fnlint.config.json" },
+        { "path": "packages/api", "config": { "ls": { ".ts": ["snake_case"] } } }
+      ] }"#,
+    )
+    .unwrap();
+    let (config, trace) = FilenameLintConfig::load_traced(&path).unwrap();
+    assert_eq!(config.roots[0].path, "packages/web");
+    assert_eq!(*config.roots[0].config.ls[".ts"].cases, vec![FilenameCase::Kebab]);
+    assert_eq!(*config.roots[1].config.ls[".ts"].cases, vec![FilenameCase::Snake]);
+    assert_eq!(trace.sources.len(), 2);
+
+    std::fs::write(
+      web.join("fnlint.config.json"),
+      r#"{ "ls": {}, "roots": [{ "path": "..", "config": "../../fnlint.config.json" }] }"#,
+    )
+    .unwrap();
+    let error = FilenameLintConfig::load_traced(&path).unwrap_err();
+    assert!(error.to_string().starts_with("Circular `roots`"), "{}", error);
+  }
+
+  #[test]
+  fn load_each_format() {
+    let json =
+      load_fixture("fnlint.config.json", r#"{ "ls": { ".rs": ["snake_case"] }, "ignore": [] }"#);
+    assert_eq!(json.unwrap().ls[".rs"], FilenameRule::from(vec![FilenameCase::Snake]));
+
+    let yaml = load_fixture("fnlint.config.yaml", "ls:\n  .rs: [snake_case]\nignore: []\n");
+    #[cfg(feature = "yaml")]
+    assert_eq!(yaml.unwrap().ls[".rs"], FilenameRule::from(vec![FilenameCase::Snake]));
+    #[cfg(not(feature = "yaml"))]
+    assert!(yaml.unwrap_err().to_string().contains("without the `yaml` feature"));
+
+    let toml =
+      load_fixture("fnlint.config.toml", "ignore = []\n[ls]\n\".rs\" = [\"snake_case\"]\n");
+    #[cfg(feature = "toml")]
+    assert_eq!(toml.unwrap().ls[".rs"], FilenameRule::from(vec![FilenameCase::Snake]));
+    #[cfg(not(feature = "toml"))]
+    assert!(toml.unwrap_err().to_string().contains("without the `toml` feature"));
+  }
+
+  #[test]
+  fn load_utf16_config() {
+    fn write(name: &str, bom: &[u8], units: Vec<[u8; 2]>) -> Result<FilenameLintConfig> {
+      let dir = tempfile::tempdir().unwrap();
+      let path = dir.path().join(name);
+      std::fs::write(&path, [bom.to_vec(), units.concat()].concat()).unwrap();
+      FilenameLintConfig::load_traced(&path).map(|(config, _)| config)
+    }
+    let json = r#"{ "ls": { ".rs": ["snake_case"] }, "ignore": ["target"] }"#;
+    let be = json.encode_utf16().map(u16::to_be_bytes).collect();
+    assert_eq!(write("fnlint.config.json", &[0xFE, 0xFF], be).unwrap().ignore, vec!["target"]);
+    #[cfg(feature = "toml")]
+    {
+      let toml = "ignore = [\"target\"]\n[ls]\n\".rs\" = [\"snake_case\"]\n";
+      let le = toml.encode_utf16().map(u16::to_le_bytes).collect();
+      assert_eq!(write("fnlint.config.toml", &[0xFF, 0xFE], le).unwrap().ignore, vec!["target"]);
+    }
+    let error = write("fnlint.config.json", &[], vec![[0xC3, 0x28]]).unwrap_err();
+    assert!(error.to_string().contains("is not UTF-8"), "{}", error);
+  }
+
+  #[test]
+  fn reject_huge_regex() {
+    let error = load_fixture(
+      "fnlint.config.json",
+      r#"{ "ls": {}, "ignore": [], "generatedMarker": "(a|b){50000}" }"#,
+    )
+    .unwrap_err();
+    assert!(error.to_string().contains("exceeds size limit"), "{}", error);
+    let small = r#"{ "ls": {}, "ignore": [], "generatedMarker": "@generated by [a-z]+" }"#;
+    assert!(load_fixture("fnlint.config.json", small).is_ok());
+    let limited = r#"{ "ls": {}, "ignore": [], "generatedMarker": "@generated by [a-z]+", "regexSizeLimit": 64 }"#;
+    assert!(load_fixture("fnlint.config.json", limited).is_err());
+  }
+
+  #[test]
+  fn parse_rule_forms() {
+    let config: FilenameLintConfig = serde_json::from_str(
+      r#"{
+        "ls": {
+          ".rs": ["snake_case"],
+          ".html": { "cases": ["kebab-case"], "allowNumericOnly": false },
+          ".ts": { "cases": ["camelCase"], "allowAcronyms": true },
+          ".md": { "cases": ["kebab-case"], "fullPath": true, "allowAcronymDirs": 3, "skipDirs": 1 },
+          ".json": { "cases": ["point.case"], "allowDotsInStem": false, "severity": "warning" },
+          ".tsx": {
+            "cases": ["kebab-case"],
+            "exceptions": [{ "pattern": "^use[A-Z]", "cases": ["camelCase"] }]
+          }
+        },
+        "ignore": []
+      }"#,
+    )
+    .unwrap();
+    assert_eq!(config.ls[".rs"], FilenameRule::from(vec![FilenameCase::Snake]));
+    assert_eq!(
+      config.ls[".html"],
+      FilenameRule { allow_numeric_only: false, ..FilenameRule::from(vec![FilenameCase::Kebab]) }
+    );
+    assert!(config.ls[".ts"].case_options.allow_acronyms);
+    assert_eq!(config.ls[".md"].allow_acronym_dirs, 3);
+    assert_eq!(config.ls[".md"].skip_dirs, 1);
+    assert!(!config.ls[".json"].allow_dots_in_stem);
+    assert_eq!(config.ls[".json"].severity, Severity::Warning);
+    let exception = &config.ls[".tsx"].exceptions[0];
+    assert_eq!(exception.pattern.0.as_str(), "^use[A-Z]");
+    assert_eq!(*exception.cases, vec![FilenameCase::Camel]);
+  }
+
+  #[test]
+  fn extension_keys_without_dot() {
+    let config = load_fixture(
+      "fnlint.config.json",
+      r#"{ "ls": { "ts": "kebab-case", "style": "kebab-case", "src/*.ts": "kebab-case" },
+        "categories": { "style": ["css", ".scss"] }, "ignore": [] }"#,
+    )
+    .unwrap();
+    assert_eq!(config.ls.keys().collect::<Vec<_>>(), vec![".ts", "style", "src/*.ts"]);
+    assert_eq!(config.categories["style"], vec![".css", ".scss"]);
+    let files = ["lib/userService.ts", "lib/user-service.ts", "lib/Theme.css"].map(String::from);
+    let issues = crate::linter::visitor::lint_filenames(&config, &files).issues;
+    let paths = issues.iter().map(|issue| issue.path.as_str()).collect::<Vec<_>>();
+    assert_eq!(paths, vec!["lib/userService.ts", "lib/Theme.css"]);
+
+    let error = load_fixture(
+      "fnlint.config.json",
+      r#"{ "ls": { "ts": "kebab-case", ".ts": "camelCase" }, "ignore": [] }"#,
+    )
+    .unwrap_err();
+    assert_eq!(error.to_string(), "Duplicate key in `ls`: `ts` and `.ts` both name `.ts`");
+  }
+
+  #[test]
+  fn parse_single_case() {
+    let config = load_fixture(
+      "fnlint.config.json",
+      r#"{ "ls": { ".rs": "snake_case", ".ts": ["kebab-case"] }, "ignore": [] }"#,
+    )
+    .unwrap();
+    assert_eq!(*config.ls[".rs"].cases, vec![FilenameCase::Snake]);
+    assert_eq!(config.ls[".rs"], FilenameRule::from(vec![FilenameCase::Snake]));
+    assert!(
+      load_fixture("fnlint.config.json", r#"{ "ls": { ".rs": "snake" }, "ignore": [] }"#).is_ok()
+    );
+    assert!(
+      load_fixture("fnlint.config.json", r#"{ "ls": { ".rs": "nope" }, "ignore": [] }"#).is_err()
+    );
+  }
+
+  #[test]
+  fn parse_case_whitespace() {
+    let config: FilenameLintConfig =
+      serde_json::from_str(r#"{ "ls": { ".rs": ["snake_case ", "\tkebab-case"] }, "ignore": [] }"#)
+        .unwrap();
+    assert_eq!(
+      config.ls[".rs"],
+      FilenameRule::from(vec![FilenameCase::Snake, FilenameCase::Kebab])
+    );
+    let error = serde_json::from_str::<FilenameLintConfig>(
+      r#"{ "ls": { ".rs": ["snake_case\u200b"] }, "ignore": [] }"#,
+    )
+    .unwrap_err();
+    assert!(error.to_string().starts_with(r#"Unknown filename case: "snake_case\u{200b}""#));
+  }
+
+  #[test]
+  fn parse_case_aliases() {
+    let aliases = [
+      ("lower", FilenameCase::Lower),
+      ("snake", FilenameCase::Snake),
+      ("camel", FilenameCase::Camel),
+      ("camelcase", FilenameCase::Camel),
+      ("kebab", FilenameCase::Kebab),
+      ("pascal", FilenameCase::Pascal),
+      ("pascalcase", FilenameCase::Pascal),
+      ("PascalCase", FilenameCase::Pascal),
+      ("point", FilenameCase::Point),
+      ("screaming_snake", FilenameCase::ScreamingSnake),
+      ("SCREAMING_SNAKE", FilenameCase::ScreamingSnake),
+    ];
+    for (alias, case) in aliases {
+      assert_eq!(FilenameCase::from_str(alias), Ok(case), "{}", alias);
+    }
+    for case in FilenameCase::ALL {
+      assert_eq!(FilenameCase::from_str(&case.to_string()), Ok(case));
+    }
+    assert_eq!(FilenameCase::Kebab.to_string(), "kebab-case");
+    assert!(FilenameCase::from_str("KEBAB").is_err());
+  }
+
+  #[test]
+  fn detect_case() {
+    assert_eq!(FilenameCase::detect("utils"), Some(FilenameCase::Lower));
+    assert_eq!(FilenameCase::detect("hello_world"), Some(FilenameCase::Snake));
+    assert_eq!(FilenameCase::detect("hello-world"), Some(FilenameCase::Kebab));
+    assert_eq!(FilenameCase::detect("helloWorld"), Some(FilenameCase::Camel));
+    assert_eq!(FilenameCase::detect("HelloWorld"), Some(FilenameCase::Pascal));
+    assert_eq!(FilenameCase::detect("HELLO_WORLD"), Some(FilenameCase::ScreamingSnake));
+    assert_eq!(FilenameCase::detect("hello world"), None);
+  }
+
+  #[test]
+  fn match_full_filename() {
+    let options = CaseOptions::default();
+    assert!(FilenameCase::Kebab.matches_full("user-service.ts", &options));
+    assert!(FilenameCase::Point.matches_full("user.service.ts", &options));
+    assert!(!FilenameCase::Kebab.matches_full("user-service.TS", &options));
+    assert!(!FilenameCase::Kebab.matches_full("userService.ts", &options));
+    assert!(FilenameCase::Kebab.matches_full("makefile", &options));
+    assert!(FilenameCase::ScreamingSnake.matches_full("README.MD", &options));
+    assert!(FilenameCase::ScreamingSnake.matches_full("README.md", &options));
+  }
+
+  #[test]
+  fn detect_precedence() {
+    use FilenameCase::*;
+    assert_eq!(FilenameCase::detect_all("hello"), vec![Lower, Snake, Kebab, Point, Camel]);
+    assert_eq!(FilenameCase::detect("hello"), Some(Lower));
+    assert_eq!(FilenameCase::detect_all("hello_world"), vec![Snake]);
+    assert_eq!(FilenameCase::detect("hello_world"), Some(Snake));
+    assert_eq!(FilenameCase::detect_all("A1"), vec![Pascal, ScreamingSnake]);
+  }
+
+  #[test]
+  fn case_option_matrix() {
+    // (name, case, needs acronyms, needs leading digit, needs trailing digit)
+    let names = [
+      ("helloWorld", FilenameCase::Camel, false, false, false),
+      ("URLParser", FilenameCase::Camel, true, false, false),
+      ("2dPoint", FilenameCase::Camel, false, true, false),
+      ("http2Server", FilenameCase::Camel, false, false, true),
+      ("2URLParser", FilenameCase::Camel, true, true, false),
+      ("HelloWorld", FilenameCase::Pascal, false, false, false),
+      ("Vector3", FilenameCase::Pascal, false, false, false),
+      ("HTMLParser", FilenameCase::Pascal, true, false, false),
+      ("2FactorAuth", FilenameCase::Pascal, false, true, false),
+      ("3DModel", FilenameCase::Pascal, true, true, false),
+    ];
+    for variant in 0..CaseOptions::VARIANTS {
+      let options = CaseOptions::from_variant(variant);
+      for (name, case, acronyms, leading, trailing) in names {
+        let expected = (!acronyms || options.allow_acronyms)
+          && (!leading || options.allow_leading_digit)
+          && (!trailing || options.allow_trailing_digit);
+        assert_eq!(case.matches(name, &options), expected, "{} with {:?}", name, options);
+      }
+    }
+  }
+
+  #[test]
+  fn strict_capitals_matrix() {
+    // (name, case, passes lenient, passes strict)
+    let names = [
+      ("aBTest", FilenameCase::Camel, true, false),
+      ("parseHTMLParser", FilenameCase::Camel, true, true),
+      ("iOSApp", FilenameCase::Camel, true, true),
+      ("parseHTML", FilenameCase::Camel, true, true),
+      ("getX", FilenameCase::Camel, true, false),
+      ("ABTest", FilenameCase::Pascal, false, false),
+      ("TestAB", FilenameCase::Pascal, true, true),
+      ("TestABCase", FilenameCase::Pascal, true, true),
+      ("TestACase", FilenameCase::Pascal, true, false),
+    ];
+    for (name, case, lenient, strict) in names {
+      let options = CaseOptions::default();
+      assert_eq!(case.matches(name, &options), lenient, "{} lenient", name);
+      let options = CaseOptions { strict_capitals: true, ..options };
+      assert_eq!(case.matches(name, &options), strict, "{} strict", name);
+    }
+  }
 }
@@ -0,0 +1,76 @@
+use crate::config::{FilenameCase, FilenameLintConfig, UserRegex, SPELLINGS};
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Schema, SchemaObject};
+use schemars::{schema_for, JsonSchema};
+use serde_json::{json, Value};
+
+/// A root's `config` as written in the file, before [`FilenameLintConfig::load_traced`] inlines
+/// the ones given as a path. Only used to describe the schema.
+#[derive(JsonSchema)]
+#[serde(untagged)]
+#[allow(dead_code)]
+pub(crate) enum RootConfig {
+  Path(String),
+  Inline(Box<FilenameLintConfig>),
+}
+
+impl JsonSchema for FilenameCase {
+  fn schema_name() -> String {
+    "FilenameCase".to_string()
+  }
+
+  fn json_schema(_: &mut SchemaGenerator) -> Schema {
+    let spellings = SPELLINGS.iter().map(|(spelling, _)| json!(spelling)).collect();
+    SchemaObject {
+      instance_type: Some(InstanceType::String.into()),
+      enum_values: Some(spellings),
+      ..Default::default()
+    }
+    .into()
+  }
+}
+
+impl JsonSchema for UserRegex {
+  fn schema_name() -> String {
+    "Regex".to_string()
+  }
+
+  fn json_schema(_: &mut SchemaGenerator) -> Schema {
+    SchemaObject {
+      instance_type: Some(InstanceType::String.into()),
+      format: Some("regex".to_string()),
+      ..Default::default()
+    }
+    .into()
+  }
+}
+
+/// A JSON Schema of the configuration file, for editors to validate and complete it with.
+pub fn config_schema() -> Value {
+  let mut schema = serde_json::to_value(schema_for!(FilenameLintConfig)).unwrap();
+  // `extends` is resolved before the config is deserialized, so it is not a field
+  schema["properties"]["extends"] = json!({
+    "description": "A base config, relative to this one, whose keys this config overrides.",
+    "type": "string",
+  });
+  schema
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn schema_lists_every_case() {
+    let schema = config_schema();
+    let cases = schema["definitions"]["FilenameCase"]["enum"].as_array().unwrap();
+    for case in FilenameCase::ALL {
+      assert!(cases.contains(&json!(case.to_string())), "{} is missing", case);
+    }
+    assert_eq!(schema["required"], json!(["ignore", "ls"]));
+    let rule = &schema["definitions"]["DetailedRule"]["properties"];
+    assert_eq!(rule["severity"]["$ref"], "#/definitions/Severity");
+    assert!(rule["allowAcronyms"].is_object());
+    assert!(schema["properties"]["roots"].is_object());
+  }
+}
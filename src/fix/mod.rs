@@ -0,0 +1,327 @@
+use crate::config::convert::split_stem;
+use crate::config::FilenameCase;
+use crate::linter::Issue;
+use anyhow::Result;
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::path::Path;
+
+/// A file rename that resolves a case issue.
+#[derive(Debug, PartialEq)]
+pub struct Rename {
+  pub from: String,
+  pub to: String,
+}
+
+impl Rename {
+  fn for_issue(issue: &Issue, normalize_extension_case: bool) -> Option<Rename> {
+    if !issue.fixable() {
+      return None;
+    }
+    let suggestion = issue.suggestion()?;
+    let (parent, basename) = match issue.path.rsplit_once('/') {
+      Some((parent, basename)) => (Some(parent), basename),
+      None => (None, issue.path.as_str()),
+    };
+    let ext = basename.strip_prefix(issue.filename.as_str())?;
+    let renamed = match normalize_extension_case {
+      true => format!("{}{}", suggestion, ext.to_lowercase()),
+      false => format!("{}{}", suggestion, ext),
+    };
+    if renamed == basename {
+      return None;
+    }
+    let to = match parent {
+      Some(parent) => format!("{}/{}", parent, renamed),
+      None => renamed,
+    };
+    Some(Rename { from: issue.path.clone(), to })
+  }
+}
+
+/// Plan one rename per fixable issue. When several files would end up with the same name, only
+/// the first is renamed; the others are left for the user to resolve.
+///
+/// The extension is kept as is, unless `normalize_extension_case` asks for it to be lowercased
+/// too, so `MyComponent.JS` becomes `my-component.js` rather than `my-component.JS`.
+pub fn plan_renames(issues: &[Issue], normalize_extension_case: bool) -> Vec<Rename> {
+  unique_targets(
+    issues.iter().filter_map(|issue| Rename::for_issue(issue, normalize_extension_case)),
+  )
+}
+
+/// Plan renaming every file to `case`, whatever its rule allows, e.g. for a one-time migration.
+/// Only the name up to the first dot is converted, so `userService.test.ts` becomes
+/// `user-service.test.ts`; dotfiles are left alone. `acronyms` are kept whole, as with
+/// `knownAcronyms`. Collisions are handled as in [`plan_renames`].
+pub fn plan_case_renames(files: &[String], case: FilenameCase, acronyms: &[String]) -> Vec<Rename> {
+  let renames = files.iter().filter_map(|path| {
+    let (parent, basename) = match path.rsplit_once('/') {
+      Some((parent, basename)) => (Some(parent), basename),
+      None => (None, path.as_str()),
+    };
+    let (stem, ext) = split_stem(basename);
+    if stem.is_empty() {
+      return None;
+    }
+    let renamed = format!("{}{}", case.convert_with(stem, acronyms), ext);
+    if renamed == basename {
+      return None;
+    }
+    let to = match parent {
+      Some(parent) => format!("{}/{}", parent, renamed),
+      None => renamed,
+    };
+    Some(Rename { from: path.clone(), to })
+  });
+  unique_targets(renames)
+}
+
+fn unique_targets(renames: impl Iterator<Item = Rename>) -> Vec<Rename> {
+  let mut targets = HashSet::new();
+  renames.filter(|rename| targets.insert(rename.to.clone())).collect()
+}
+
+/// The file operations renames need, so they can be tried against a simulated filesystem.
+trait Fs {
+  fn exists(&self, path: &str) -> bool;
+  /// Whether both paths name the same file, as `Foo.ts` and `foo.ts` do on a case-insensitive
+  /// filesystem.
+  fn same_file(&self, a: &str, b: &str) -> bool;
+  fn rename(&mut self, from: &str, to: &str) -> std::io::Result<()>;
+}
+
+struct RealFs;
+
+impl Fs for RealFs {
+  fn exists(&self, path: &str) -> bool {
+    Path::new(path).exists()
+  }
+
+  #[cfg(unix)]
+  fn same_file(&self, a: &str, b: &str) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (std::fs::metadata(a), std::fs::metadata(b)) {
+      (Ok(a), Ok(b)) => (a.dev(), a.ino()) == (b.dev(), b.ino()),
+      _ => false,
+    }
+  }
+
+  // without inode numbers, trust that an existing target differing only in case is the source
+  #[cfg(not(unix))]
+  fn same_file(&self, a: &str, b: &str) -> bool {
+    a.to_lowercase() == b.to_lowercase()
+  }
+
+  fn rename(&mut self, from: &str, to: &str) -> std::io::Result<()> {
+    std::fs::rename(from, to)
+  }
+}
+
+/// Perform the renames, skipping any whose target already exists. Returns the renames done.
+///
+/// A rename whose target is the source of another waits for that one, and a case-only rename
+/// such as `Foo.ts` to `foo.ts` goes through a temporary name, since some case-insensitive
+/// filesystems treat it as a no-op.
+pub fn apply_renames(renames: Vec<Rename>) -> Result<Vec<Rename>> {
+  apply_renames_in(&mut RealFs, renames)
+}
+
+fn apply_renames_in(fs: &mut impl Fs, renames: Vec<Rename>) -> Result<Vec<Rename>> {
+  let mut pending = renames
+    .into_iter()
+    .map(|rename| {
+      let case_only = rename.from.to_lowercase() == rename.to.to_lowercase();
+      (rename.from.clone(), rename, case_only)
+    })
+    .collect::<Vec<_>>();
+  let mut applied = vec![];
+  while !pending.is_empty() {
+    let blocked = |to: &str, pending: &[(String, Rename, bool)]| {
+      pending.iter().any(|(current, ..)| current == to)
+    };
+    let Some(index) = pending.iter().position(|(_, rename, _)| !blocked(&rename.to, &pending))
+    else {
+      // a cycle, e.g. `a.ts` and `b.ts` swapping names: park one file under a temporary name
+      let current = &mut pending[0].0;
+      let temporary = temporary_name(fs, current);
+      fs.rename(current, &temporary)?;
+      *current = temporary;
+      continue;
+    };
+    let (current, rename, case_only) = pending.remove(index);
+    if fs.exists(&rename.to) && !(case_only && fs.same_file(&current, &rename.to)) {
+      eprintln!("Skipping {}: {} already exists", rename.from, rename.to);
+      continue;
+    }
+    if case_only {
+      let temporary = temporary_name(fs, &current);
+      fs.rename(&current, &temporary)?;
+      fs.rename(&temporary, &rename.to)?;
+    } else {
+      fs.rename(&current, &rename.to)?;
+    }
+    applied.push(rename);
+  }
+  Ok(applied)
+}
+
+fn temporary_name(fs: &impl Fs, path: &str) -> String {
+  (0..).map(|n| format!("{}.fnlint-{}", path, n)).find(|name| !fs.exists(name)).unwrap()
+}
+
+/// Quote `value` for a POSIX shell, leaving plain paths untouched.
+fn shell_quote(value: &str) -> String {
+  let plain = value.chars().all(|c| c.is_ascii_alphanumeric() || "_-./".contains(c));
+  match plain && !value.is_empty() {
+    true => value.to_string(),
+    false => format!("'{}'", value.replace('\'', r"'\''")),
+  }
+}
+
+/// Renders the renames as a shell script of `git mv` commands, without touching any file.
+pub struct RenameScript<'a>(pub &'a [Rename]);
+
+impl Display for RenameScript<'_> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    for rename in self.0 {
+      writeln!(f, "git mv {} {}", shell_quote(&rename.from), shell_quote(&rename.to))?;
+    }
+    Ok(())
+  }
+}
+
+impl Display for Rename {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Renamed {} to {}", self.from, self.to)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::config::{FilenameCase, FilenameRule};
+  use crate::linter::lint_files;
+
+  fn issues(files: &[&str]) -> Vec<Issue> {
+    let rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    lint_files(files.iter().map(|file| file.to_string()).collect(), ".ts".to_string(), &rule)
+  }
+
+  #[test]
+  fn test_rename_script() {
+    let issues =
+      issues(&["src/userService.ts", "src/ok-name.ts", "OrderItem.ts", "src/My File.ts"]);
+    let script = RenameScript(&plan_renames(&issues, false)).to_string();
+    assert_eq!(
+      script,
+      "git mv src/userService.ts src/user-service.ts\n\
+       git mv OrderItem.ts order-item.ts\n\
+       git mv 'src/My File.ts' src/my-file.ts\n"
+    );
+  }
+
+  #[test]
+  fn test_extension_case() {
+    let rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    let issues = lint_files(vec!["src/MyComponent.JS".to_string()], String::new(), &rule);
+    let target = |normalize| plan_renames(&issues, normalize).pop().unwrap().to;
+    assert_eq!(target(false), "src/my-component.JS");
+    assert_eq!(target(true), "src/my-component.js");
+  }
+
+  #[test]
+  fn test_case_renames() {
+    let files = ["src/userService.ts", "src/OrderItem.tsx", "src/date_utils.test.ts", "src/ok.ts"]
+      .map(String::from)
+      .to_vec();
+    let files =
+      [files, vec![".eslintrc.js".to_string(), "src/user_service.ts".to_string()]].concat();
+    let renames = plan_case_renames(&files, FilenameCase::Kebab, &[]);
+    let targets = renames.iter().map(|rename| rename.to.as_str()).collect::<Vec<_>>();
+    assert_eq!(
+      targets,
+      vec!["src/user-service.ts", "src/order-item.tsx", "src/date-utils.test.ts"]
+    );
+  }
+
+  /// A case-insensitive, case-preserving filesystem on which renaming a file to another case of
+  /// its own name does nothing, as on some network shares.
+  #[derive(Default)]
+  struct CaseInsensitiveFs {
+    files: Vec<String>,
+  }
+
+  impl CaseInsensitiveFs {
+    fn find(&self, path: &str) -> Option<usize> {
+      self.files.iter().position(|file| file.to_lowercase() == path.to_lowercase())
+    }
+  }
+
+  impl Fs for CaseInsensitiveFs {
+    fn exists(&self, path: &str) -> bool {
+      self.find(path).is_some()
+    }
+
+    fn same_file(&self, a: &str, b: &str) -> bool {
+      self.find(a).is_some() && self.find(a) == self.find(b)
+    }
+
+    fn rename(&mut self, from: &str, to: &str) -> std::io::Result<()> {
+      let index = self.find(from).ok_or(std::io::ErrorKind::NotFound)?;
+      match self.find(to) {
+        Some(target) if target == index => {}
+        Some(_) => return Err(std::io::ErrorKind::AlreadyExists.into()),
+        None => self.files[index] = to.to_string(),
+      }
+      Ok(())
+    }
+  }
+
+  #[test]
+  fn test_case_only_rename() {
+    let mut fs = CaseInsensitiveFs { files: vec!["Foo.ts".into(), "Bar.ts".into()] };
+    let renames = vec![Rename { from: "Foo.ts".into(), to: "foo.ts".into() }];
+    let applied = apply_renames_in(&mut fs, renames).unwrap();
+    assert_eq!(applied.len(), 1);
+    assert_eq!(fs.files, vec!["foo.ts", "Bar.ts"]);
+
+    let renames = vec![
+      Rename { from: "Bar.ts".into(), to: "foo.ts".into() },
+      Rename { from: "foo.ts".into(), to: "Bar.TS".into() },
+    ];
+    assert!(apply_renames_in(&mut fs, renames).unwrap().is_empty());
+    assert_eq!(fs.files, vec!["foo.ts", "Bar.ts"]);
+  }
+
+  #[test]
+  fn test_rename_order() {
+    let mut fs = CaseInsensitiveFs { files: vec!["a.ts".into(), "b.ts".into(), "c.ts".into()] };
+    // `b.ts` exists, but is renamed away before `a.ts` takes its name
+    let renames = vec![
+      Rename { from: "a.ts".into(), to: "b.ts".into() },
+      Rename { from: "b.ts".into(), to: "d.ts".into() },
+      Rename { from: "c.ts".into(), to: "e.ts".into() },
+    ];
+    let applied = apply_renames_in(&mut fs, renames).unwrap();
+    let order = applied.iter().map(|rename| rename.from.as_str()).collect::<Vec<_>>();
+    assert_eq!(order, vec!["b.ts", "a.ts", "c.ts"]);
+    assert_eq!(fs.files, vec!["b.ts", "d.ts", "e.ts"]);
+
+    let renames = vec![
+      Rename { from: "b.ts".into(), to: "d.ts".into() },
+      Rename { from: "d.ts".into(), to: "b.ts".into() },
+    ];
+    assert_eq!(apply_renames_in(&mut fs, renames).unwrap().len(), 2);
+    assert_eq!(fs.files, vec!["d.ts", "b.ts", "e.ts"]);
+  }
+
+  #[test]
+  fn test_colliding_renames() {
+    let renames = plan_renames(&issues(&["src/userService.ts", "src/UserService.ts"]), false);
+    assert_eq!(
+      renames,
+      vec![Rename { from: "src/userService.ts".into(), to: "src/user-service.ts".into() }]
+    );
+  }
+}
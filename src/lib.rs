@@ -0,0 +1,11 @@
+pub mod config;
+pub mod fix;
+pub mod linter;
+pub mod output;
+pub mod scan;
+#[cfg(feature = "async")]
+pub mod stream;
+pub mod watch;
+
+pub use config::convert::convert_case;
+pub use linter::{first_violation, is_valid_name};
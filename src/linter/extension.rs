@@ -0,0 +1,32 @@
+use crate::linter::{extension_start, Issue, IssueKind};
+
+/// Report every file whose last extension is not one of `allowed`, e.g. a stray `.orig` or
+/// `.bak` left behind by a merge or an editor. Files without an extension are left alone.
+pub fn lint_allowed_extensions(allowed: &[String], files: &[String]) -> Vec<Issue> {
+  files
+    .iter()
+    .filter_map(|path| {
+      let basename = path.rsplit('/').next().unwrap_or(path);
+      let ext = &basename[extension_start(basename)?..];
+      if allowed.iter().any(|allowed| allowed == ext) {
+        return None;
+      }
+      Some(Issue::new(IssueKind::DisallowedExtension { ext: ext.to_string() }, basename, path))
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_disallowed_extension() {
+    let allowed = [".ts".to_string(), ".rs".to_string()];
+    let files = ["src/main.rs", "src/user.service.ts", "src/main.rs.orig", "Makefile"];
+    let issues = lint_allowed_extensions(&allowed, &files.map(String::from));
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].path, "src/main.rs.orig");
+    assert_eq!(issues[0].kind, IssueKind::DisallowedExtension { ext: ".orig".to_string() });
+  }
+}
@@ -0,0 +1,132 @@
+use crate::linter::visitor::LintError;
+use crate::linter::{Issue, IssueKind};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// The files whose imports are checked.
+const SOURCE_EXTENSIONS: [&str; 8] = [".js", ".jsx", ".mjs", ".cjs", ".ts", ".tsx", ".mts", ".cts"];
+
+/// The extensions tried, in order, for a specifier naming none, as bundlers and TypeScript do.
+const RESOLVED_EXTENSIONS: [&str; 6] = [".ts", ".tsx", ".d.ts", ".js", ".jsx", ".json"];
+
+/// The string of an `import ... from`, `export ... from`, side-effect `import`, dynamic
+/// `import()` or `require()`. A lightweight match rather than a parse, so a specifier in a
+/// comment or string is checked too; only relative ones are ever looked up, which keeps that
+/// harmless in practice.
+static SPECIFIER: LazyLock<Regex> = LazyLock::new(|| {
+  Regex::new(r#"(?:\bfrom\s*|\bimport\s*\(?\s*|\brequire\s*\(\s*)['"]([^'"\n]+)['"]"#).unwrap()
+});
+
+/// The path `specifier` names from the directory `dir`, with `.` and `..` resolved lexically.
+/// `None` when it climbs above the scan root.
+fn join(dir: &str, specifier: &str) -> Option<String> {
+  let mut parts = dir.split('/').filter(|part| !part.is_empty()).collect::<Vec<_>>();
+  for part in specifier.split('/') {
+    match part {
+      "" | "." => {}
+      ".." => {
+        parts.pop()?;
+      }
+      part => parts.push(part),
+    }
+  }
+  Some(parts.join("/"))
+}
+
+/// The files a relative import of `target` may mean, in the order they are tried.
+fn candidates(target: &str) -> Vec<String> {
+  let mut candidates = vec![target.to_string()];
+  candidates.extend(RESOLVED_EXTENSIONS.iter().map(|ext| format!("{}{}", target, ext)));
+  candidates.extend(RESOLVED_EXTENSIONS.iter().map(|ext| format!("{}/index{}", target, ext)));
+  candidates
+}
+
+/// Report the relative imports of JS and TS files that only resolve when case is ignored, e.g.
+/// `import Foo from './fooBar'` next to `FooBar.ts`. Such an import works on macOS and Windows
+/// but breaks on case-sensitive filesystems. Files are looked up among `files`, so an import of
+/// a file that was not scanned is not checked. Sources are read relative to `base`; those that
+/// cannot be read are returned apart.
+pub fn lint_import_case(files: &[String], base: &Path) -> (Vec<Issue>, Vec<LintError>) {
+  let exact = files.iter().map(String::as_str).collect::<HashSet<_>>();
+  let folded =
+    files.iter().map(|file| (file.to_lowercase(), file.as_str())).collect::<HashMap<_, _>>();
+  let (mut issues, mut errors) = (vec![], vec![]);
+  for path in files.iter().filter(|file| SOURCE_EXTENSIONS.iter().any(|ext| file.ends_with(ext))) {
+    let content = match std::fs::read(base.join(path)) {
+      Ok(content) => String::from_utf8_lossy(&content).into_owned(),
+      Err(error) => {
+        errors.push(LintError { path: base.join(path).display().to_string(), error });
+        continue;
+      }
+    };
+    let (dir, basename) = path.rsplit_once('/').unwrap_or(("", path));
+    for capture in SPECIFIER.captures_iter(&content) {
+      let specifier = &capture[1];
+      if !specifier.starts_with("./") && !specifier.starts_with("../") {
+        continue;
+      }
+      let Some(target) = join(dir, specifier) else {
+        continue;
+      };
+      for candidate in candidates(&target) {
+        if exact.contains(candidate.as_str()) {
+          break;
+        }
+        if let Some(actual) = folded.get(&candidate.to_lowercase()) {
+          let kind =
+            IssueKind::ImportCase { specifier: specifier.to_string(), file: actual.to_string() };
+          issues.push(Issue::new(kind, basename, path));
+          break;
+        }
+      }
+    }
+  }
+  (issues, errors)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::fs;
+
+  #[test]
+  fn import_case_mismatch() {
+    let dir = tempfile::tempdir().unwrap();
+    let sources = [
+      ("src/app.ts", "import Foo from './fooBar'\nimport { x } from \"../lib/Utils\";\n"),
+      ("src/FooBar.ts", "export const bar = require('./ok');\nimport('./components');\n"),
+      ("src/ok.js", "import React from 'react';\nimport './styles.CSS';\n"),
+      ("src/components/index.tsx", "export * from '../fooBar';\nimport '../../../outside';\n"),
+      ("src/styles.css", ""),
+      ("lib/utils.ts", ""),
+    ];
+    for (path, content) in sources {
+      let path = dir.path().join(path);
+      fs::create_dir_all(path.parent().unwrap()).unwrap();
+      fs::write(path, content).unwrap();
+    }
+    let files = sources.map(|(path, _)| path.to_string());
+    let (issues, errors) = lint_import_case(&files, dir.path());
+    assert!(errors.is_empty());
+    let found = issues.iter().map(|issue| (issue.path.as_str(), &issue.kind)).collect::<Vec<_>>();
+    let kind = |specifier: &str, file: &str| IssueKind::ImportCase {
+      specifier: specifier.to_string(),
+      file: file.to_string(),
+    };
+    assert_eq!(
+      found,
+      vec![
+        ("src/app.ts", &kind("./fooBar", "src/FooBar.ts")),
+        ("src/app.ts", &kind("../lib/Utils", "lib/utils.ts")),
+        ("src/ok.js", &kind("./styles.CSS", "src/styles.css")),
+        ("src/components/index.tsx", &kind("../fooBar", "src/FooBar.ts")),
+      ]
+    );
+    assert_eq!(
+      issues[0].to_string(),
+      "Import ./fooBar in src/app.ts does not match the case of src/FooBar.ts"
+    );
+  }
+}
@@ -0,0 +1,131 @@
+use crate::config::{is_single_word, FilenameCase, FilenameRule, NONE_SPLIT};
+use regex::Regex;
+use std::sync::Arc;
+
+/// All cases of a rule compiled into one alternation, so each stem is tested with a single
+/// regex search instead of one per case.
+pub struct RuleMatcher {
+  regex: Option<Regex>,
+  /// Without `allowSingleWord`, and unless `lowercase` is one of the cases.
+  rejects_single_word: bool,
+  exceptions: Vec<(Regex, Arc<Vec<FilenameCase>>, RuleMatcher)>,
+}
+
+impl RuleMatcher {
+  pub fn new(rule: &FilenameRule) -> Self {
+    let mut matcher = Self::for_cases(&rule.cases, rule);
+    matcher.exceptions = rule
+      .exceptions
+      .iter()
+      .map(|exception| {
+        let cases = exception.cases.clone();
+        let matcher = Self::for_cases(&cases, rule);
+        (exception.pattern.0.clone(), cases, matcher)
+      })
+      .collect();
+    matcher
+  }
+
+  fn for_cases(cases: &[FilenameCase], rule: &FilenameRule) -> Self {
+    let rejects_single_word = !rule.allow_single_word && !cases.contains(&FilenameCase::Lower);
+    if cases.is_empty() {
+      return RuleMatcher { regex: None, rejects_single_word, exceptions: vec![] };
+    }
+    let options = &rule.case_options;
+    let alternatives = std::iter::once(NONE_SPLIT.to_string())
+      .chain(cases.iter().map(|case| case.pattern(options)))
+      .map(|pattern| format!("(?:{})", pattern))
+      .collect::<Vec<String>>();
+    let regex = Some(Regex::new(&alternatives.join("|")).unwrap());
+    RuleMatcher { regex, rejects_single_word, exceptions: vec![] }
+  }
+
+  /// The matcher and cases of the first exception whose pattern matches `stem`, if any.
+  pub fn exception(&self, stem: &str) -> Option<(&RuleMatcher, &Arc<Vec<FilenameCase>>)> {
+    self
+      .exceptions
+      .iter()
+      .find(|(pattern, ..)| pattern.is_match(stem))
+      .map(|(_, cases, matcher)| (matcher, cases))
+  }
+
+  /// Whether `stem` fits one of the cases. A stem of only digits counts as no word at all.
+  pub fn is_match(&self, stem: &str) -> bool {
+    if self.rejects_single_word
+      && is_single_word(stem)
+      && stem.contains(|c: char| c.is_ascii_alphabetic())
+    {
+      return false;
+    }
+    self.regex.as_ref().is_some_and(|regex| regex.is_match(stem))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::config::CaseOptions;
+
+  #[test]
+  fn single_word_per_rule() {
+    let rule = |case, allow_single_word| {
+      let rule = FilenameRule { allow_single_word, ..FilenameRule::from(vec![case]) };
+      RuleMatcher::new(&rule)
+    };
+    let (strict_snake, kebab) = (rule(FilenameCase::Snake, false), rule(FilenameCase::Kebab, true));
+    assert!(!strict_snake.is_match("utils"));
+    assert!(strict_snake.is_match("date_utils"));
+    assert!(strict_snake.is_match("404"));
+    assert!(kebab.is_match("utils"));
+    assert!(rule(FilenameCase::Snake, true).is_match("utils"));
+    assert!(!rule(FilenameCase::Kebab, false).is_match("utils"));
+    assert!(rule(FilenameCase::Lower, false).is_match("utils"));
+  }
+
+  #[test]
+  fn combined_matches_each_case() {
+    let names = [
+      "mod",
+      "hello_world",
+      "hello-world",
+      "helloWorld",
+      "HelloWorld",
+      "hello.world",
+      "HELLO",
+      "HELLO_WORLD",
+      "URLParser",
+      "2dPoint",
+      "http2Server",
+      "a_bC",
+      "hello world",
+      "404",
+      "",
+    ];
+    let options = [
+      CaseOptions::default(),
+      CaseOptions {
+        allow_acronyms: true,
+        allow_leading_digit: true,
+        allow_trailing_digit: true,
+        strict_capitals: true,
+      },
+    ];
+    for cases in [
+      vec![],
+      vec![FilenameCase::Snake],
+      vec![FilenameCase::Kebab, FilenameCase::Camel],
+      vec![FilenameCase::Pascal, FilenameCase::ScreamingSnake, FilenameCase::Point],
+      FilenameCase::ALL.to_vec(),
+    ] {
+      for case_options in options {
+        let mut rule = FilenameRule::from(cases.clone());
+        rule.case_options = case_options;
+        let matcher = RuleMatcher::new(&rule);
+        for name in names {
+          let expected = cases.iter().any(|case| case.matches(name, &case_options));
+          assert_eq!(matcher.is_match(name), expected, "{} against {:?}", name, cases);
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,71 @@
+use crate::config::TestFileConfig;
+use crate::linter::{Issue, IssueKind};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Reduce a stem to its words, so `userService`, `user-service` and `UserService` compare equal.
+fn loose_name(stem: &str) -> String {
+  stem.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+/// For every test file, e.g. `userService.test.ts`, check that `userService.ts` exists in the
+/// same directory. A source that only exists under another case is reported as mis-cased.
+pub fn lint_test_files(config: &TestFileConfig, files: &[String]) -> Vec<Issue> {
+  let scanned = files.iter().map(String::as_str).collect::<HashSet<&str>>();
+  files
+    .iter()
+    .filter_map(|path| {
+      let file = Path::new(path);
+      let parent = file.parent()?;
+      let filename = file.file_name()?.to_str()?;
+      let (name, ext) = filename.rsplit_once('.')?;
+      let stem = config.suffixes.iter().find_map(|suffix| name.strip_suffix(suffix.as_str()))?;
+      let source = parent.join(format!("{}.{}", stem, ext));
+      if scanned.contains(source.to_str()?) {
+        return None;
+      }
+      let sibling = files.iter().find(|other| {
+        let other = Path::new(other);
+        other.parent() == Some(parent)
+          && other.extension().and_then(|e| e.to_str()) == Some(ext)
+          && other.file_stem().and_then(|s| s.to_str()).map(loose_name) == Some(loose_name(stem))
+      });
+      let kind = match sibling {
+        Some(source) => IssueKind::MiscasedTest { source: source.clone() },
+        None => IssueKind::OrphanTest,
+      };
+      Some(Issue::new(kind, filename, path))
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn lint(files: &[&str]) -> Vec<Issue> {
+    let config = TestFileConfig { suffixes: vec![".test".to_string(), ".spec".to_string()] };
+    lint_test_files(&config, &files.iter().map(|file| file.to_string()).collect::<Vec<_>>())
+  }
+
+  #[test]
+  fn test_matched_pair() {
+    assert!(lint(&["src/userService.ts", "src/userService.test.ts"]).is_empty());
+    assert!(lint(&["src/userService.ts", "src/userService.spec.ts"]).is_empty());
+  }
+
+  #[test]
+  fn test_orphan_test_file() {
+    let issues = lint(&["src/userService.ts", "src/orderService.test.ts"]);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].kind, IssueKind::OrphanTest);
+    assert_eq!(issues[0].path, "src/orderService.test.ts");
+  }
+
+  #[test]
+  fn test_miscased_test_file() {
+    let issues = lint(&["src/user-service.ts", "src/userService.test.ts"]);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].kind, IssueKind::MiscasedTest { source: "src/user-service.ts".into() });
+  }
+}
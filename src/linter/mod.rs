@@ -1,47 +1,409 @@
-use crate::config::FilenameCase;
+use crate::config::{FilenameCase, FilenameRule, Severity};
+use crate::linter::matcher::RuleMatcher;
+use crate::scan::path::normalize_path;
+use std::collections::HashMap;
 use std::fmt::Display;
-use std::sync::Arc;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+pub mod extension;
+#[cfg(feature = "imports")]
+pub mod imports;
+pub mod matcher;
+pub mod mirror;
+pub mod required;
+pub mod rule;
+pub mod selftest;
+pub mod shadow;
+pub mod spelling;
 pub mod visitor;
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum IssueKind {
+  /// The filename matches none of the `target` cases.
+  Case,
+  /// A test file without a source file of the same name next to it.
+  OrphanTest,
+  /// A test file whose source file exists, but under a differently-cased name.
+  MiscasedTest { source: String },
+  /// With `fullPath`, a directory (the issue's `filename`) matches none of the `target` cases.
+  DirectoryCase,
+  /// A file without an extension under a rule with `requireExtension`.
+  MissingExtension,
+  /// A file named like a sibling directory, e.g. `utils.ts` next to `utils/`.
+  ShadowedDirectory { dir: String },
+  /// A directory, the issue's `path`, lacks a file that `requiredFiles` asks for.
+  MissingFile { file: String },
+  /// A file whose extension `allowedExtensions` does not list.
+  DisallowedExtension { ext: String },
+  /// A file no `ls` rule applies to, under `unmatchedFiles`.
+  Unmatched,
+  /// A word of the filename that `spelling` does not know, such as `recieve`.
+  UnknownWord { word: String },
+  /// Under a rule with `localeSuffix`, a last dotted segment of the stem that is no locale,
+  /// such as `english` in `messages.english.json`.
+  InvalidLocale { locale: String },
+  /// Under `importCase`, a relative import, `specifier`, that only resolves to `file` when case
+  /// is ignored.
+  ImportCase { specifier: String, file: String },
+  /// Reported by a [`Rule`](rule::Rule) of another crate, which names the kind, e.g.
+  /// `max-length`, and words the issue.
+  Custom { name: String, message: String },
+}
+
+#[derive(Debug, Clone)]
 pub struct Issue {
   pub filename: String,
   pub target: Arc<Vec<FilenameCase>>,
   pub path: String,
+  pub kind: IssueKind,
+  /// The rule's message template, used instead of the default text.
+  pub message: Option<Arc<str>>,
+  /// What reported the issue: the `ls` key of the rule, or the name of the config option
+  /// enabling the check, e.g. `testFiles`. Empty when linting outside of a config.
+  pub rule: String,
+  pub severity: Severity,
+  /// The config's `knownAcronyms`, kept whole when suggesting a name.
+  pub acronyms: Arc<Vec<String>>,
+}
+
+impl Issue {
+  /// An issue that is not tied to any expected case.
+  pub fn new(kind: IssueKind, filename: &str, path: &str) -> Self {
+    Issue {
+      filename: filename.to_string(),
+      target: Arc::new(vec![]),
+      path: path.to_string(),
+      kind,
+      message: None,
+      rule: String::new(),
+      severity: Severity::Error,
+      acronyms: Arc::new(vec![]),
+    }
+  }
+
+  /// The same issue, attributed to `rule`.
+  pub fn with_rule(self, rule: &str) -> Self {
+    Issue { rule: rule.to_string(), ..self }
+  }
+
+  /// The same issue, with its paths prefixed by the root directory `dir` it was found under.
+  pub fn under(self, dir: &str) -> Self {
+    let kind = match self.kind {
+      IssueKind::MiscasedTest { source } => {
+        IssueKind::MiscasedTest { source: format!("{}/{}", dir, source) }
+      }
+      IssueKind::ShadowedDirectory { dir: shadowed } => {
+        IssueKind::ShadowedDirectory { dir: format!("{}/{}", dir, shadowed) }
+      }
+      IssueKind::ImportCase { specifier, file } => {
+        IssueKind::ImportCase { specifier, file: format!("{}/{}", dir, file) }
+      }
+      kind => kind,
+    };
+    Issue { path: format!("{}/{}", dir, self.path), kind, ..self }
+  }
+
+  /// The filename rewritten in the first expected case. The rule's own order is kept rather than
+  /// [`FilenameCase::ALL`], so the case listed first in the config is the one files are fixed to.
+  pub fn suggestion(&self) -> Option<String> {
+    self.target.first().map(|case| case.convert_with(&self.filename, &self.acronyms))
+  }
+
+  /// Whether renaming the file to its [`suggestion`](Self::suggestion) resolves the issue, which
+  /// is what `--fix` does. Only a name in the wrong case qualifies; a missing file or a stray
+  /// extension needs a person to decide.
+  pub fn fixable(&self) -> bool {
+    self.kind == IssueKind::Case && self.suggestion().is_some_and(|name| name != self.filename)
+  }
+
+  fn cases(&self) -> String {
+    self.target.iter().map(|target| target.to_string()).collect::<Vec<String>>().join(", ")
+  }
+}
+
+/// Issues are equal when they report the same kind of problem for the same file and expected
+/// cases; the message template does not take part.
+impl PartialEq for Issue {
+  fn eq(&self, other: &Self) -> bool {
+    self.path == other.path
+      && self.filename == other.filename
+      && self.target == other.target
+      && self.kind == other.kind
+  }
+}
+
+impl Eq for Issue {}
+
+impl Hash for Issue {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.path.hash(state);
+    self.filename.hash(state);
+    self.target.hash(state);
+    self.kind.hash(state);
+  }
 }
 
 impl Display for Issue {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    write!(f, "Filename {} in {} does not match any of the patterns: ", self.filename, self.path)?;
-    let cases =
-      self.target.iter().map(|target| target.to_string()).collect::<Vec<String>>().join(", ");
-    write!(f, "{}", cases)?;
+    if let Some(message) = &self.message {
+      let message = message
+        .replace("{filename}", &self.filename)
+        .replace("{cases}", &self.cases())
+        .replace("{suggestion}", &self.suggestion().unwrap_or_default());
+      return write!(f, "{}", message);
+    }
+    match &self.kind {
+      IssueKind::Case => {
+        write!(
+          f,
+          "Filename {} in {} does not match any of the patterns: ",
+          self.filename, self.path
+        )?;
+        write!(f, "{}", self.cases())?;
+      }
+      IssueKind::OrphanTest => {
+        write!(f, "Test file {} in {} has no matching source file", self.filename, self.path)?
+      }
+      IssueKind::MiscasedTest { source } => write!(
+        f,
+        "Test file {} in {} does not match the case of its source file {}",
+        self.filename, self.path, source
+      )?,
+      IssueKind::MissingExtension => {
+        write!(f, "File {} in {} has no extension", self.filename, self.path)?
+      }
+      IssueKind::ShadowedDirectory { dir } => {
+        write!(f, "File {} shadows the directory {}", self.path, dir)?
+      }
+      IssueKind::MissingFile { file } => {
+        write!(f, "Directory {} is missing the required file {}", self.path, file)?
+      }
+      IssueKind::DisallowedExtension { ext } => {
+        write!(f, "File {} has the disallowed extension {}", self.path, ext)?
+      }
+      IssueKind::Unmatched => write!(f, "File {} is not covered by any rule", self.path)?,
+      IssueKind::UnknownWord { word } => {
+        write!(f, "Filename {} in {} contains the unknown word {}", self.filename, self.path, word)?
+      }
+      IssueKind::Custom { message, .. } => write!(f, "{}", message)?,
+      IssueKind::ImportCase { specifier, file } => {
+        write!(f, "Import {} in {} does not match the case of {}", specifier, self.path, file)?
+      }
+      IssueKind::InvalidLocale { locale } => write!(
+        f,
+        "Filename {} in {} ends in {}, which is not a locale such as en-US or zh-Hans",
+        self.filename, self.path, locale
+      )?,
+      IssueKind::DirectoryCase => {
+        write!(
+          f,
+          "Directory {} in {} does not match any of the patterns: ",
+          self.filename, self.path
+        )?;
+        write!(f, "{}", self.cases())?;
+      }
+    }
     Ok(())
   }
 }
 
-pub fn lint_files(
-  files: Vec<String>,
-  ext: String,
-  patterns: &Arc<Vec<FilenameCase>>,
-) -> Vec<Issue> {
-  files.iter().filter_map(|path| lint_name(path, &patterns, &ext)).collect::<Vec<Issue>>()
+/// An `ls` rule made ready to check names with: its cases, severity, exceptions and options, the
+/// compiled matcher, and the extension stripped from a name before its stem is checked.
+pub struct RuleContext<'a> {
+  pub rule: &'a FilenameRule,
+  pub matcher: RuleMatcher,
+  pub ext: String,
+  /// The [`Verdict`] of every basename checked so far, since monorepos repeat names like
+  /// `index.ts` and `mod.rs` thousands of times. A context lives for one run of one rule, so the
+  /// basename is the whole key.
+  memo: Option<Mutex<HashMap<String, Verdict>>>,
 }
 
-fn lint_name(path: &str, patterns: &Arc<Vec<FilenameCase>>, ext: &str) -> Option<Issue> {
-  let filename = path.split('/').last()?;
-  // trim `ext` content
-  let filename = filename.trim_end_matches(ext);
-  for pattern in patterns.iter() {
-    if pattern.matches(filename) {
-      return None;
+/// Whether a stem passes a rule, and if not, the cases it is held to.
+type Verdict = Option<Arc<Vec<FilenameCase>>>;
+
+impl<'a> RuleContext<'a> {
+  pub fn new(rule: &'a FilenameRule, ext: impl Into<String>) -> Self {
+    let memo = Some(Mutex::default());
+    RuleContext { rule, matcher: RuleMatcher::new(rule), ext: ext.into(), memo }
+  }
+
+  /// Check every name afresh instead of remembering the outcome per basename.
+  pub fn without_memo(self) -> Self {
+    RuleContext { memo: None, ..self }
+  }
+
+  /// The cases `stem`, the checked part of `basename`, fails, or `None` when it passes.
+  fn verdict(&self, basename: &str, stem: &str) -> Verdict {
+    let Some(memo) = &self.memo else {
+      return self.check_stem(basename, stem);
+    };
+    if let Some(verdict) = memo.lock().unwrap().get(basename) {
+      return verdict.clone();
     }
+    let verdict = self.check_stem(basename, stem);
+    memo.lock().unwrap().insert(basename.to_string(), verdict.clone());
+    verdict
+  }
+
+  fn check_stem(&self, basename: &str, stem: &str) -> Verdict {
+    let RuleContext { rule, matcher, .. } = self;
+    let (matcher, target) = match matcher.exception(stem) {
+      Some((matcher, cases)) => (matcher, cases),
+      None => (matcher, &rule.cases),
+    };
+    let numeric_only = !stem.is_empty() && stem.chars().all(|c| c.is_ascii_digit());
+    let rejected = (numeric_only && !rule.allow_numeric_only)
+      || (stem.contains('.') && !rule.allow_dots_in_stem);
+    let ext = &basename[stem.len()..];
+    let extension_allowed =
+      !rule.include_extension || target.iter().any(|case| case.extension_matches(ext));
+    let allowed = !rejected && extension_allowed && matcher.is_match(stem);
+    (!allowed).then(|| target.clone())
   }
-  Some(Issue { filename: filename.to_string(), target: patterns.clone(), path: path.to_string() })
+}
+
+pub fn lint_files(files: Vec<String>, ext: String, rule: &FilenameRule) -> Vec<Issue> {
+  let ctx = RuleContext::new(rule, ext);
+  files.iter().flat_map(|path| lint_file(path, &ctx)).collect()
+}
+
+/// Check a proposed filename, e.g. `UserCard.tsx` from a scaffolding tool, against `cases`
+/// without touching the filesystem. As under a glob key, the last extension is not checked.
+/// Only the part after the last `/` is looked at, so directories are not checked either.
+pub fn first_violation(name: &str, cases: &[FilenameCase]) -> Option<Issue> {
+  let rule = FilenameRule::from(cases.to_vec());
+  lint_name(name, &RuleContext::new(&rule, ""))
+}
+
+/// Whether `name` passes [`first_violation`].
+pub fn is_valid_name(name: &str, cases: &[FilenameCase]) -> bool {
+  first_violation(name, cases).is_none()
+}
+
+/// Check the name of one file, and with `full_path` its directories, against the rule.
+pub fn lint_file(path: &str, ctx: &RuleContext) -> Vec<Issue> {
+  let mut issues = lint_name(path, ctx).into_iter().collect::<Vec<Issue>>();
+  if ctx.rule.full_path {
+    issues.extend(lint_dirs(path, ctx));
+  }
+  issues.iter_mut().for_each(|issue| issue.severity = ctx.rule.severity);
+  issues
+}
+
+/// Check each directory of `path` against the rule, except the first `skip_dirs` ones and short
+/// all-caps acronyms when `allow_acronym_dirs` permits them.
+fn lint_dirs(path: &str, ctx: &RuleContext) -> Vec<Issue> {
+  let RuleContext { rule, matcher, .. } = ctx;
+  let path = normalize_path(path);
+  let Some((dirs, _)) = path.rsplit_once('/') else {
+    return vec![];
+  };
+  dirs
+    .split('/')
+    .skip(rule.skip_dirs)
+    .filter(|dir| !matcher.is_match(dir))
+    .filter(|dir| {
+      let acronym =
+        dir.len() <= rule.allow_acronym_dirs && dir.chars().all(|c| c.is_ascii_uppercase());
+      !acronym
+    })
+    .map(|dir| Issue {
+      target: rule.cases.clone(),
+      message: rule.message.clone(),
+      ..Issue::new(IssueKind::DirectoryCase, dir, &path)
+    })
+    .collect()
+}
+
+/// The part of `basename` that is checked against the rule's cases, i.e. `basename` without
+/// `ext`. Only one `ext` is removed, so `a.rs.rs` gives `a.rs`. A basename that does not end in
+/// `ext` (such as `rs` for `.rs`), or that is nothing but `ext` (a dotfile like `.rs`), has no
+/// stem and is not linted.
+///
+/// An empty `ext`, from a glob key naming no extension such as `src/**`, strips each file's own
+/// last extension instead, so `user.service.ts` gives `user.service`. A dotfile such as
+/// `.eslintrc` then has no stem either.
+fn stem<'a>(basename: &'a str, ext: &str) -> Option<&'a str> {
+  if ext.is_empty() {
+    return match extension_start(basename) {
+      Some(dot) => Some(&basename[..dot]),
+      None => Some(basename).filter(|basename| !basename.starts_with('.')),
+    };
+  }
+  basename.strip_suffix(ext).filter(|stem| !stem.is_empty())
+}
+
+/// `stem` without a last dotted segment listed in `qualifiers`, so `styles.min` gives `styles`
+/// when `min` is allowed. A stem that is nothing but the qualifier is kept whole.
+fn strip_qualifier<'a>(stem: &'a str, qualifiers: &[String]) -> &'a str {
+  qualifiers
+    .iter()
+    .find_map(|qualifier| {
+      let rest = stem.strip_suffix(qualifier.trim_start_matches('.'))?.strip_suffix('.')?;
+      Some(rest).filter(|rest| !rest.is_empty())
+    })
+    .unwrap_or(stem)
+}
+
+/// Whether `segment` reads as a locale: two lowercase letters, then optionally a dash and a
+/// region or script, as in `en`, `en-US` and `zh-Hans`.
+fn is_locale(segment: &str) -> bool {
+  let (language, subtag) = match segment.split_once('-') {
+    Some((language, subtag)) => (language, Some(subtag)),
+    None => (segment, None),
+  };
+  language.len() == 2
+    && language.chars().all(|c| c.is_ascii_lowercase())
+    && subtag.map_or(true, |subtag| {
+      !subtag.is_empty() && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+    })
+}
+
+/// Where the last extension of `basename` starts. A leading dot does not start one, so dotfiles
+/// such as `.gitignore` have no extension.
+fn extension_start(basename: &str) -> Option<usize> {
+  basename.rfind('.').filter(|dot| *dot > 0)
+}
+
+fn lint_name(path: &str, ctx: &RuleContext) -> Option<Issue> {
+  let RuleContext { rule, ext, .. } = ctx;
+  let path = normalize_path(path);
+  let basename = path.split('/').last()?;
+  if rule.require_extension && extension_start(basename).is_none() {
+    return Some(Issue {
+      message: rule.message.clone(),
+      ..Issue::new(IssueKind::MissingExtension, basename, &path)
+    });
+  }
+  let mut filename = strip_qualifier(stem(basename, ext)?, &rule.allow_qualifiers);
+  if rule.locale_suffix {
+    if let Some((rest, locale)) = filename.rsplit_once('.').filter(|(rest, _)| !rest.is_empty()) {
+      if !is_locale(locale) {
+        return Some(Issue {
+          message: rule.message.clone(),
+          ..Issue::new(IssueKind::InvalidLocale { locale: locale.to_string() }, filename, &path)
+        });
+      }
+      filename = rest;
+    }
+  }
+  let target = ctx.verdict(basename, filename)?;
+  Some(Issue {
+    target,
+    message: rule.message.clone(),
+    ..Issue::new(IssueKind::Case, filename, &path)
+  })
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::config::{CaseException, UserRegex};
+
+  fn lint(path: &str, rule: &FilenameRule, ext: &str) -> Option<Issue> {
+    lint_name(path, &RuleContext::new(rule, ext))
+  }
 
   #[test]
   fn test_issue_print() {
@@ -49,76 +411,122 @@ mod tests {
       filename: "hello-world.js".to_string(),
       target: Arc::new(vec![FilenameCase::Kebab, FilenameCase::Lower]),
       path: "src/linter/helloWorld.js".to_string(),
+      kind: IssueKind::Case,
+      message: None,
+      rule: ".js".to_string(),
+      severity: Severity::Error,
+      acronyms: Arc::new(vec![]),
     };
     let expected =
       "Filename hello-world.js in src/linter/helloWorld.js does not match any of the patterns: kebab-case, lowercase";
     assert_eq!(issue.to_string(), expected);
   }
 
+  #[test]
+  fn test_issue_dedup() {
+    let first = lint("src/helloWorld.js", &FilenameRule::from(vec![FilenameCase::Kebab]), ".js");
+    let mut second =
+      lint("./src//helloWorld.js", &FilenameRule::from(vec![FilenameCase::Kebab]), ".js");
+    second.as_mut().unwrap().message = Some("custom".into());
+    assert_eq!(first, second);
+    let snake = lint("src/helloWorld.js", &FilenameRule::from(vec![FilenameCase::Snake]), ".js");
+    assert_ne!(first, snake);
+    let issues = std::collections::HashSet::from([first.unwrap(), second.unwrap()]);
+    assert_eq!(issues.len(), 1);
+  }
+
+  #[test]
+  fn lint_exceptions() {
+    let mut rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    rule.exceptions = vec![CaseException {
+      pattern: UserRegex(regex::Regex::new("^use[A-Z]").unwrap()),
+      cases: Arc::new(vec![FilenameCase::Camel]),
+    }];
+    assert!(lint("src/hooks/useFetch.ts", &rule, ".ts").is_none());
+    assert!(lint("src/date-utils.ts", &rule, ".ts").is_none());
+    let issue = lint("src/MyComponent.ts", &rule, ".ts").unwrap();
+    assert_eq!(*issue.target, vec![FilenameCase::Kebab]);
+    let issue = lint("src/use_fetch.ts", &rule, ".ts").unwrap();
+    assert_eq!(*issue.target, vec![FilenameCase::Kebab]);
+    let issue = lint("src/useFETCH_now.ts", &rule, ".ts").unwrap();
+    assert_eq!(*issue.target, vec![FilenameCase::Camel]);
+  }
+
+  #[test]
+  fn test_custom_message() {
+    let mut rule = FilenameRule::from(vec![FilenameCase::Kebab, FilenameCase::Lower]);
+    rule.message = Some("{filename} should be {cases}: try {suggestion}. See our guide.".into());
+    let issue = lint("src/linter/helloWorld.js", &rule, ".js").unwrap();
+    assert_eq!(
+      issue.to_string(),
+      "helloWorld should be kebab-case, lowercase: try hello-world. See our guide."
+    );
+  }
+
   #[test]
   fn lint_none_case() {
-    let patterns = Arc::new(vec![FilenameCase::Kebab, FilenameCase::Lower]);
-    let no_issue = lint_name("src/linter/mod.rs", &patterns, ".rs").is_none();
+    let patterns = FilenameRule::from(vec![FilenameCase::Kebab, FilenameCase::Lower]);
+    let no_issue = lint("src/linter/mod.rs", &patterns, ".rs").is_none();
     assert!(no_issue);
   }
 
   #[test]
   fn lint_kebab_case() {
-    let patterns = Arc::new(vec![FilenameCase::Kebab]);
-    let no_issue = lint_name("src/linter/hello-world.js", &patterns, ".js");
+    let patterns = FilenameRule::from(vec![FilenameCase::Kebab]);
+    let no_issue = lint("src/linter/hello-world.js", &patterns, ".js");
     assert!(no_issue.is_none());
-    let camel = lint_name("src/linter/helloWorld.js", &patterns, ".js");
+    let camel = lint("src/linter/helloWorld.js", &patterns, ".js");
     assert!(camel.is_some());
-    let pascal = lint_name("src/linter/HelloWorld.js", &patterns, ".js");
+    let pascal = lint("src/linter/HelloWorld.js", &patterns, ".js");
     assert!(pascal.is_some());
-    let snake = lint_name("src/linter/hello_world.js", &patterns, ".js");
+    let snake = lint("src/linter/hello_world.js", &patterns, ".js");
     assert!(snake.is_some());
   }
 
   #[test]
   fn lint_camel_case() {
-    let patterns = Arc::new(vec![FilenameCase::Camel]);
-    let always_good = lint_name("src/linter/mod.js", &patterns, ".js").is_none();
+    let patterns = FilenameRule::from(vec![FilenameCase::Camel]);
+    let always_good = lint("src/linter/mod.js", &patterns, ".js").is_none();
     assert!(always_good);
-    let no_issue = lint_name("src/linter/helloWorld.js", &patterns, ".js").is_none();
+    let no_issue = lint("src/linter/helloWorld.js", &patterns, ".js").is_none();
     assert!(no_issue);
-    let kebab = lint_name("src/linter/hello-world.js", &patterns, ".js");
+    let kebab = lint("src/linter/hello-world.js", &patterns, ".js");
     assert!(kebab.is_some());
-    let pascal = lint_name("src/linter/HelloWorld.js", &patterns, ".js");
+    let pascal = lint("src/linter/HelloWorld.js", &patterns, ".js");
     assert!(pascal.is_some());
-    let snake = lint_name("src/linter/hello_world.js", &patterns, ".js");
+    let snake = lint("src/linter/hello_world.js", &patterns, ".js");
     assert!(snake.is_some());
   }
 
   #[test]
   fn lint_pascal_case() {
-    let patterns = Arc::new(vec![FilenameCase::Pascal]);
-    let no_issue = lint_name("src/linter/HelloWorld.js", &patterns, ".js").is_none();
+    let patterns = FilenameRule::from(vec![FilenameCase::Pascal]);
+    let no_issue = lint("src/linter/HelloWorld.js", &patterns, ".js").is_none();
     assert!(no_issue);
-    let kebab = lint_name("src/linter/hello-world.js", &patterns, ".js");
+    let kebab = lint("src/linter/hello-world.js", &patterns, ".js");
     assert!(kebab.is_some());
-    let camel = lint_name("src/linter/helloWorld.js", &patterns, ".js");
+    let camel = lint("src/linter/helloWorld.js", &patterns, ".js");
     assert!(camel.is_some());
-    let snake = lint_name("src/linter/hello_world.js", &patterns, ".js");
+    let snake = lint("src/linter/hello_world.js", &patterns, ".js");
     assert!(snake.is_some());
   }
 
   #[test]
   fn lint_snake_case() {
-    let patterns = Arc::new(vec![FilenameCase::Snake]);
-    let no_issue = lint_name("src/linter/hello_world.js", &patterns, ".js").is_none();
+    let patterns = FilenameRule::from(vec![FilenameCase::Snake]);
+    let no_issue = lint("src/linter/hello_world.js", &patterns, ".js").is_none();
     assert!(no_issue);
-    let kebab = lint_name("src/linter/hello-world.js", &patterns, ".js");
+    let kebab = lint("src/linter/hello-world.js", &patterns, ".js");
     assert!(kebab.is_some());
-    let camel = lint_name("src/linter/helloWorld.js", &patterns, ".js");
+    let camel = lint("src/linter/helloWorld.js", &patterns, ".js");
     assert!(camel.is_some());
-    let pascal = lint_name("src/linter/HelloWorld.js", &patterns, ".js");
+    let pascal = lint("src/linter/HelloWorld.js", &patterns, ".js");
     assert!(pascal.is_some());
   }
 
   #[test]
   fn lint_snake_files() {
-    let patterns = Arc::new(vec![FilenameCase::Snake]);
+    let patterns = FilenameRule::from(vec![FilenameCase::Snake]);
     let files = vec![
       "src/linter/hello_world.js".to_string(),
       "src/linter/a_bC.js".to_string(),
@@ -130,4 +538,212 @@ mod tests {
     let issues = lint_files(files, ".js".to_string(), &patterns);
     assert_eq!(issues.len(), 5);
   }
+
+  #[test]
+  fn lint_normalized_path() {
+    let patterns = FilenameRule::from(vec![FilenameCase::Snake]);
+    let issue = lint(".\\src\\linter\\helloWorld.js", &patterns, ".js").unwrap();
+    assert_eq!(issue.filename, "helloWorld");
+    assert_eq!(issue.path, "src/linter/helloWorld.js");
+  }
+
+  #[test]
+  fn lint_numeric_only() {
+    let mut rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    assert!(lint("public/404.html", &rule, ".html").is_none());
+    rule.allow_numeric_only = false;
+    assert!(lint("public/404.html", &rule, ".html").is_some());
+    assert!(lint("public/not-found.html", &rule, ".html").is_none());
+  }
+
+  #[test]
+  fn lint_dots_in_stem() {
+    let mut rule = FilenameRule::from(vec![FilenameCase::Point]);
+    assert!(lint("config/a.b.json", &rule, ".json").is_none());
+    rule.allow_dots_in_stem = false;
+    assert_eq!(lint("config/a.b.json", &rule, ".json").unwrap().filename, "a.b");
+    assert!(lint("config/ab.json", &rule, ".json").is_none());
+  }
+
+  #[test]
+  fn lint_qualifiers() {
+    let mut rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    rule.allow_qualifiers = vec!["min".to_string(), ".prod".to_string()];
+    assert!(lint("assets/styles.min.css", &rule, ".css").is_none());
+    assert!(lint("assets/app-shell.prod.js", &rule, ".js").is_none());
+    assert!(lint("assets/min.css", &rule, ".css").is_none());
+    assert_eq!(lint("assets/styles.weird.css", &rule, ".css").unwrap().filename, "styles.weird");
+    let issue = lint("assets/MainStyles.min.css", &rule, ".css").unwrap();
+    assert_eq!(
+      (issue.filename.as_str(), issue.suggestion()),
+      ("MainStyles", Some("main-styles".into()))
+    );
+  }
+
+  #[test]
+  fn lint_locale_suffix() {
+    let mut rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    rule.locale_suffix = true;
+    assert!(lint("locales/messages.en-US.json", &rule, ".json").is_none());
+    assert!(lint("locales/messages.zh-Hans.json", &rule, ".json").is_none());
+    assert!(lint("locales/messages.fr.json", &rule, ".json").is_none());
+    assert!(lint("locales/messages.json", &rule, ".json").is_none());
+    let issue = lint("locales/messages.english.json", &rule, ".json").unwrap();
+    assert_eq!(issue.kind, IssueKind::InvalidLocale { locale: "english".to_string() });
+    assert_eq!(
+      issue.to_string(),
+      "Filename messages.english in locales/messages.english.json ends in english, which is not \
+       a locale such as en-US or zh-Hans"
+    );
+    assert!(!issue.fixable());
+    assert!(lint("locales/messages.en_US.json", &rule, ".json").is_some());
+    assert_eq!(
+      lint("locales/userMessages.en-US.json", &rule, ".json").unwrap().filename,
+      "userMessages"
+    );
+  }
+
+  #[test]
+  fn lint_require_extension() {
+    let mut rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    rule.require_extension = true;
+    let files = ["src/README", "src/user-service.ts", "src/.eslintrc", "src/a.b"].map(String::from);
+    let issues = lint_files(files.to_vec(), String::new(), &rule);
+    let paths = issues.iter().map(|issue| issue.path.as_str()).collect::<Vec<_>>();
+    assert_eq!(paths, vec!["src/README", "src/.eslintrc"]);
+    assert_eq!(issues[0].kind, IssueKind::MissingExtension);
+    assert_eq!(issues[0].to_string(), "File README in src/README has no extension");
+  }
+
+  #[test]
+  fn stem_edge_cases() {
+    assert_eq!(stem("main.rs", ".rs"), Some("main"));
+    assert_eq!(stem("a.rs.rs", ".rs"), Some("a.rs"));
+    assert_eq!(stem(".rs", ".rs"), None);
+    assert_eq!(stem("rs", ".rs"), None);
+    assert_eq!(stem("user.service.ts", ""), Some("user.service"));
+    assert_eq!(stem("README", ""), Some("README"));
+    assert_eq!(stem(".gitignore", ""), None);
+    assert_eq!(stem("users", ".rs"), None);
+    let rule = FilenameRule::from(vec![FilenameCase::Snake]);
+    assert!(lint("src/.rs", &rule, ".rs").is_none());
+    assert!(lint("src/.eslintrc", &rule, "").is_none());
+    assert_eq!(lint("src/a.rs.rs", &rule, ".rs").unwrap().filename, "a.rs");
+  }
+
+  #[test]
+  fn lint_acronym_dirs() {
+    let mut rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    rule.full_path = true;
+    let files = ["src/API/client.ts", "src/MyModule/client.ts"].map(String::from).to_vec();
+    let issues = lint_files(files.clone(), ".ts".to_string(), &rule);
+    let dirs = issues.iter().map(|issue| issue.filename.as_str()).collect::<Vec<_>>();
+    assert_eq!(dirs, vec!["API", "MyModule"]);
+
+    rule.allow_acronym_dirs = 3;
+    let issues = lint_files(files, ".ts".to_string(), &rule);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].kind, IssueKind::DirectoryCase);
+    assert_eq!(issues[0].filename, "MyModule");
+    assert_eq!(issues[0].path, "src/MyModule/client.ts");
+  }
+
+  #[test]
+  fn lint_include_extension() {
+    let mut rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    let files = ["src/user-service.ts", "src/user-service.TS", "src/page.Test.tsx", "Makefile"];
+    let lint = |rule: &FilenameRule| {
+      let issues = lint_files(files.map(String::from).to_vec(), String::new(), rule);
+      issues.into_iter().map(|issue| issue.path).collect::<Vec<_>>()
+    };
+    assert_eq!(lint(&rule), vec!["src/page.Test.tsx", "Makefile"]);
+    rule.include_extension = true;
+    assert_eq!(lint(&rule), vec!["src/user-service.TS", "src/page.Test.tsx", "Makefile"]);
+  }
+
+  #[test]
+  fn check_proposed_names() {
+    let cases = [FilenameCase::Kebab, FilenameCase::Pascal];
+    assert!(is_valid_name("user-card.tsx", &cases));
+    assert!(is_valid_name("UserCard.tsx", &cases));
+    assert!(is_valid_name("src/components/UserCard", &cases));
+    assert!(!is_valid_name("userCard.tsx", &cases));
+    assert!(!is_valid_name("user_card.tsx", &[FilenameCase::Kebab]));
+    assert!(!is_valid_name("index.ts", &[]));
+
+    let issue = first_violation("userCard.tsx", &cases).unwrap();
+    assert_eq!(issue.filename, "userCard");
+    assert_eq!(issue.suggestion().as_deref(), Some("user-card"));
+    assert!(first_violation("user-card.tsx", &cases).is_none());
+  }
+
+  #[test]
+  fn lint_file_outcomes() {
+    let mut rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    rule.full_path = true;
+    rule.severity = Severity::Warning;
+    rule.exceptions = vec![CaseException {
+      pattern: UserRegex(regex::Regex::new("^use[A-Z]").unwrap()),
+      cases: Arc::new(vec![FilenameCase::Camel]),
+    }];
+    let ctx = RuleContext::new(&rule, ".ts");
+    let outcomes = |path| {
+      let issues = lint_file(path, &ctx);
+      issues
+        .into_iter()
+        .map(|issue| (issue.kind, issue.filename, issue.severity))
+        .collect::<Vec<_>>()
+    };
+    assert!(outcomes("src/hooks/useFetch.ts").is_empty());
+    assert!(outcomes("src/date-utils.ts").is_empty());
+    assert!(outcomes("src/date-utils.rs").is_empty());
+    assert_eq!(
+      outcomes("src/MyHooks/use_fetch.ts"),
+      vec![
+        (IssueKind::Case, "use_fetch".to_string(), Severity::Warning),
+        (IssueKind::DirectoryCase, "MyHooks".to_string(), Severity::Warning),
+      ]
+    );
+    let files = ["src/MyHooks/use_fetch.ts", "src/ok.ts"].map(String::from).to_vec();
+    assert_eq!(lint_files(files, ".ts".to_string(), &rule).len(), 2);
+  }
+
+  #[test]
+  fn memoized_lint_matches_fresh_lint() {
+    let mut rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    rule.allow_numeric_only = false;
+    rule.exceptions = vec![CaseException {
+      pattern: UserRegex(regex::Regex::new("^use[A-Z]").unwrap()),
+      cases: Arc::new(vec![FilenameCase::Camel]),
+    }];
+    let files = [
+      "src/index.ts",
+      "lib/index.ts",
+      "src/useFetch.ts",
+      "src/use_fetch.ts",
+      "src/a/UserCard.ts",
+      "src/b/UserCard.ts",
+      "src/404.ts",
+      "src/index.ts",
+    ];
+    let memoized = RuleContext::new(&rule, ".ts");
+    let fresh = RuleContext::new(&rule, ".ts").without_memo();
+    for _ in 0..2 {
+      for file in files {
+        assert_eq!(lint_file(file, &memoized), lint_file(file, &fresh), "{}", file);
+      }
+    }
+  }
+
+  #[test]
+  fn lint_skip_dirs() {
+    let mut rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    rule.full_path = true;
+    rule.skip_dirs = 1;
+    let files = ["MyPackage/src/client.ts", "MyPackage/FooBar/client.ts", "client.ts"];
+    let issues = lint_files(files.map(String::from).to_vec(), ".ts".to_string(), &rule);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].filename, "FooBar");
+    assert_eq!(issues[0].path, "MyPackage/FooBar/client.ts");
+  }
 }
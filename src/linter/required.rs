@@ -0,0 +1,62 @@
+use crate::linter::{Issue, IssueKind};
+use crate::scan::pattern::Pattern;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::Path;
+
+/// For every scanned directory matching one of the `required` globs, check that it contains
+/// each of the files listed for that glob, e.g. an `index.ts` barrel in every component folder.
+pub fn lint_required_files(
+  required: &HashMap<String, Vec<String>>,
+  files: &[String],
+) -> Vec<Issue> {
+  let scanned = files.iter().map(String::as_str).collect::<HashSet<&str>>();
+  let dirs = files
+    .iter()
+    .flat_map(|file| Path::new(file).ancestors().skip(1))
+    .filter_map(Path::to_str)
+    .filter(|dir| !dir.is_empty())
+    .collect::<BTreeSet<&str>>();
+  let mut globs = required.iter().collect::<Vec<_>>();
+  globs.sort();
+  let mut issues = vec![];
+  for (glob, names) in globs {
+    let Ok(pattern) = Pattern::exact(glob) else {
+      continue;
+    };
+    for dir in dirs.iter().filter(|dir| pattern.matches(dir)) {
+      for name in names {
+        if !scanned.contains(format!("{}/{}", dir, name).as_str()) {
+          let filename = dir.rsplit('/').next().unwrap_or(dir);
+          issues.push(Issue::new(IssueKind::MissingFile { file: name.clone() }, filename, dir));
+        }
+      }
+    }
+  }
+  issues
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_missing_barrel_file() {
+    let required = HashMap::from([("src/components/*".to_string(), vec!["index.ts".to_string()])]);
+    let files = [
+      "src/components/button/index.ts",
+      "src/components/button/button.ts",
+      "src/components/card/card.ts",
+      "src/components/card/parts/header.ts",
+      "src/utils/date.ts",
+    ]
+    .map(String::from);
+    let issues = lint_required_files(&required, &files);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].path, "src/components/card");
+    assert_eq!(issues[0].kind, IssueKind::MissingFile { file: "index.ts".to_string() });
+    assert_eq!(
+      issues[0].to_string(),
+      "Directory src/components/card is missing the required file index.ts"
+    );
+  }
+}
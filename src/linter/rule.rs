@@ -0,0 +1,368 @@
+use crate::config::FilenameLintConfig;
+use crate::linter::visitor::{glob_ext, is_glob, specificity};
+use crate::linter::{lint_file, Issue, RuleContext};
+use crate::scan::pattern::Pattern;
+use crate::scan::scanner::{is_ignored_path, IgnorePattern};
+use std::fmt::Display;
+use std::sync::Arc;
+
+/// A file handed to every [`Rule`].
+#[derive(Debug, Clone, Copy)]
+pub struct ScannedFile<'a> {
+  /// The path relative to the scan root, e.g. `src/userService.ts`.
+  pub path: &'a str,
+  /// The last component of `path`, e.g. `userService.ts`.
+  pub basename: &'a str,
+}
+
+impl<'a> ScannedFile<'a> {
+  pub fn new(path: &'a str) -> Self {
+    ScannedFile { path, basename: path.rsplit('/').next().unwrap_or(path) }
+  }
+}
+
+/// An `ls` rule as it applies to a file: the key it is declared under, and the extension that
+/// is stripped before checking the stem.
+pub struct Scope<'a> {
+  pub key: &'a str,
+  pub rule: RuleContext<'a>,
+  pattern: Option<Pattern>,
+  ignore: Vec<IgnorePattern>,
+}
+
+impl Scope<'_> {
+  /// Whether the rule's own `ignore` exempts the file at `path` from it.
+  pub fn ignores(&self, path: &str) -> bool {
+    is_ignored_path(path, &self.ignore, true)
+  }
+}
+
+/// `ls` rules that govern the same file but have no case in common, a mistake in the config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleConflict {
+  /// The first file the rules contend for.
+  pub path: String,
+  /// How many files the rules contend for, `path` included.
+  pub files: usize,
+  /// The keys of the rules, in the order they are tried.
+  pub keys: Vec<String>,
+  /// Whether the keys are equally specific globs, of which only the first declared applies,
+  /// rather than extension keys, which all apply.
+  pub tied: bool,
+}
+
+impl Display for RuleConflict {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let keys = self.keys.iter().map(|key| format!("`{}`", key)).collect::<Vec<_>>().join(", ");
+    let files = match self.files {
+      1 => self.path.clone(),
+      2 => format!("{} and 1 more file", self.path),
+      files => format!("{} and {} more files", self.path, files - 1),
+    };
+    match self.tied {
+      true => write!(
+        f,
+        "Rules {} are equally specific for {} but share no case; only {} applies, being \
+         declared first",
+        keys, files, self.keys[0]
+      ),
+      false => {
+        write!(f, "Rules {} all apply to {} but share no case, so no name passes", keys, files)
+      }
+    }
+  }
+}
+
+/// What a [`Rule`] can see besides the file it checks: the config, every file of the run, and
+/// which `ls` rules govern a file.
+pub struct LintContext<'a> {
+  pub config: &'a FilenameLintConfig,
+  pub files: &'a [String],
+  /// Glob keys, most specific first; keys of equal specificity keep their declaration order.
+  globs: Vec<Scope<'a>>,
+  /// Extension keys in declaration order, one scope per extension of a category.
+  exts: Vec<Scope<'a>>,
+  acronyms: Arc<Vec<String>>,
+}
+
+impl<'a> LintContext<'a> {
+  pub fn new(config: &'a FilenameLintConfig, files: &'a [String]) -> Self {
+    let mut globs = vec![];
+    let mut exts = vec![];
+    for (key, rule) in &config.ls {
+      let scope = |ext: String, pattern| Scope {
+        key,
+        rule: RuleContext::new(rule, ext),
+        pattern,
+        ignore: rule.ignore.iter().filter_map(|pattern| IgnorePattern::new(pattern).ok()).collect(),
+      };
+      match config.categories.get(key) {
+        Some(category) => exts.extend(category.iter().map(|ext| scope(ext.clone(), None))),
+        None if is_glob(key) => globs.push(scope(glob_ext(key), Pattern::new(key).ok())),
+        None => exts.push(scope(key.clone(), None)),
+      }
+    }
+    globs.sort_by(|a, b| specificity(b.key).cmp(&specificity(a.key)));
+    let acronyms = Arc::new(config.known_acronyms.clone());
+    LintContext { config, files, globs, exts, acronyms }
+  }
+
+  /// The `ls` rules a file is checked against: the most specific glob key matching its path if
+  /// there is one, and otherwise every extension key its name ends with. That includes rules
+  /// whose [`ignore`](Scope::ignores) exempts the file.
+  pub fn scopes(&self, path: &str) -> Vec<&Scope<'a>> {
+    let glob =
+      self.globs.iter().find(|scope| scope.pattern.as_ref().is_some_and(|p| p.matches(path)));
+    match glob {
+      Some(scope) => vec![scope],
+      None => self.exts.iter().filter(|scope| path.ends_with(&scope.rule.ext)).collect(),
+    }
+  }
+
+  /// The rules contending for the file at `path` without a case in common: the glob keys as
+  /// specific as the one [`scopes`](Self::scopes) picks, or the extension keys it returns. Rules
+  /// whose own `ignore` exempts the file do not take part.
+  pub fn conflict(&self, path: &str) -> Option<RuleConflict> {
+    let matches = |scope: &Scope| scope.pattern.as_ref().is_some_and(|p| p.matches(path));
+    let (contenders, tied) = match self.globs.iter().find(|scope| matches(scope)) {
+      Some(first) => {
+        let tied = self
+          .globs
+          .iter()
+          .filter(|scope| specificity(scope.key) == specificity(first.key) && matches(scope));
+        (tied.collect::<Vec<_>>(), true)
+      }
+      None => (self.scopes(path), false),
+    };
+    let contenders =
+      contenders.into_iter().filter(|scope| !scope.ignores(path)).collect::<Vec<_>>();
+    let exclusive = contenders.iter().enumerate().any(|(index, a)| {
+      contenders[index + 1..]
+        .iter()
+        .any(|b| !a.rule.rule.cases.iter().any(|case| b.rule.rule.cases.contains(case)))
+    });
+    let mut keys = contenders.iter().map(|scope| scope.key.to_string()).collect::<Vec<_>>();
+    keys.dedup();
+    exclusive.then(|| RuleConflict { path: path.to_string(), files: 1, keys, tied })
+  }
+}
+
+/// A check run on every scanned file. Issues left without a [`rule`](Issue::rule) are
+/// attributed to the rule's [`name`](Rule::name). A rule checking something no built-in kind
+/// describes reports [`IssueKind::Custom`](crate::linter::IssueKind::Custom) issues.
+pub trait Rule {
+  fn name(&self) -> &str;
+  /// The issues of `file`; a file may break a rule in several places, such as in more than
+  /// one directory of its path.
+  fn check(&self, file: &ScannedFile, ctx: &LintContext) -> Vec<Issue>;
+}
+
+/// The built-in check of names, and with `fullPath` directories, against the `ls` rules.
+pub struct CaseRule;
+
+impl Rule for CaseRule {
+  fn name(&self) -> &str {
+    "ls"
+  }
+
+  fn check(&self, file: &ScannedFile, ctx: &LintContext) -> Vec<Issue> {
+    ctx
+      .scopes(file.path)
+      .into_iter()
+      .filter(|scope| !scope.ignores(file.path))
+      .flat_map(|scope| {
+        let issues = lint_file(file.path, &scope.rule);
+        issues
+          .into_iter()
+          .map(|issue| Issue { acronyms: ctx.acronyms.clone(), ..issue.with_rule(scope.key) })
+      })
+      .collect()
+  }
+}
+
+/// The rules a lint run applies to each file, in the order they were registered.
+pub struct Registry {
+  rules: Vec<Box<dyn Rule>>,
+}
+
+impl Default for Registry {
+  /// The built-in rules only.
+  fn default() -> Self {
+    Registry { rules: vec![Box::new(CaseRule)] }
+  }
+}
+
+impl Registry {
+  /// A registry without any rule, not even the built-in ones.
+  pub fn empty() -> Self {
+    Registry { rules: vec![] }
+  }
+
+  /// Add a rule, e.g. one contributed by another crate, to run after those already registered.
+  pub fn register(&mut self, rule: impl Rule + 'static) -> &mut Self {
+    self.rules.push(Box::new(rule));
+    self
+  }
+
+  /// Run every rule on `file`.
+  pub fn check(&self, file: &ScannedFile, ctx: &LintContext) -> Vec<Issue> {
+    self
+      .rules
+      .iter()
+      .flat_map(|rule| {
+        rule.check(file, ctx).into_iter().map(|issue| match issue.rule.is_empty() {
+          true => issue.with_rule(rule.name()),
+          false => issue,
+        })
+      })
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::config::FilenameCase;
+  use crate::linter::visitor::lint_with;
+  use crate::linter::IssueKind;
+  use indexmap::IndexMap;
+
+  /// Flags names longer than a limit.
+  struct MaxLength(usize);
+
+  impl Rule for MaxLength {
+    fn name(&self) -> &str {
+      "maxLength"
+    }
+
+    fn check(&self, file: &ScannedFile, _: &LintContext) -> Vec<Issue> {
+      match file.basename.len() > self.0 {
+        true => {
+          let kind = IssueKind::Custom {
+            name: "max-length".to_string(),
+            message: format!("Filename {} is longer than {} characters", file.basename, self.0),
+          };
+          vec![Issue::new(kind, file.basename, file.path)]
+        }
+        false => vec![],
+      }
+    }
+  }
+
+  #[test]
+  fn custom_rule_runs_alongside_case_check() {
+    let config = FilenameLintConfig {
+      ls: IndexMap::from([(".ts".to_string(), vec![FilenameCase::Kebab].into())]),
+      ..Default::default()
+    };
+    let files =
+      ["src/a-very-long-module-name.ts", "src/OrderItem.ts", "src/ok.ts"].map(String::from);
+    let mut registry = Registry::default();
+    registry.register(MaxLength(16));
+    let issues = lint_with(&registry, &config, &files).issues;
+    let found =
+      issues.iter().map(|issue| (issue.rule.as_str(), issue.path.as_str())).collect::<Vec<_>>();
+    assert_eq!(
+      found,
+      vec![("maxLength", "src/a-very-long-module-name.ts"), (".ts", "src/OrderItem.ts")]
+    );
+    assert_eq!(
+      issues[0].to_string(),
+      "Filename a-very-long-module-name.ts is longer than 16 characters"
+    );
+    assert_eq!(issues[0].kind.name(), "max-length");
+    assert!(!issues[0].fixable());
+
+    let mut registry = Registry::empty();
+    registry.register(MaxLength(16));
+    assert_eq!(lint_with(&registry, &config, &files).issues.len(), 1);
+  }
+
+  #[test]
+  fn rule_ignore_exempts_files() {
+    let config = serde_json::from_value::<FilenameLintConfig>(serde_json::json!({
+      "ls": {
+        ".ts": { "cases": ["kebab-case"], "ignore": ["__generated__/", "src/legacy/*.ts"] },
+        ".d.ts": "kebab-case",
+      },
+      "ignore": [],
+    }))
+    .unwrap();
+    let files = [
+      "src/__generated__/ApiTypes.ts",
+      "src/__generated__/ApiTypes.d.ts",
+      "src/legacy/OldName.ts",
+      "src/legacy/nested/OldName.ts",
+      "src/BadName.ts",
+    ]
+    .map(String::from);
+    let report = lint_with(&Registry::default(), &config, &files);
+    let found = report
+      .issues
+      .iter()
+      .map(|issue| (issue.rule.as_str(), issue.path.as_str()))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      found,
+      vec![
+        (".d.ts", "src/__generated__/ApiTypes.d.ts"),
+        (".ts", "src/legacy/nested/OldName.ts"),
+        (".ts", "src/BadName.ts"),
+      ]
+    );
+    assert_eq!(report.matched[".ts"], 5);
+  }
+
+  #[test]
+  fn conflicting_rules_warn() {
+    let config = serde_json::from_value::<FilenameLintConfig>(serde_json::json!({
+      "ls": {
+        "src/*/index.ts": "kebab-case",
+        "src/components/*.ts": "PascalCase",
+        "src/**/*.ts": "camelCase",
+        ".css": "kebab-case",
+        ".module.css": "camelCase",
+      },
+      "ignore": [],
+    }))
+    .unwrap();
+    let files = [
+      "src/components/index.ts",
+      "src/hooks/index.ts",
+      "src/a/b/c.ts",
+      "app.module.css",
+      "src/page.module.css",
+      "src/nav.module.css",
+    ]
+    .map(String::from);
+    let report = lint_with(&Registry::default(), &config, &files);
+    let conflicts = report.conflicts.iter().map(ToString::to_string).collect::<Vec<_>>();
+    assert_eq!(
+      conflicts,
+      vec![
+        "Rules `src/*/index.ts`, `src/components/*.ts` are equally specific for \
+         src/components/index.ts but share no case; only src/*/index.ts applies, being declared \
+         first",
+        "Rules `.css`, `.module.css` all apply to app.module.css and 2 more files but share no \
+         case, so no name passes",
+      ]
+    );
+  }
+
+  #[test]
+  fn scopes_prefer_globs() {
+    let config = FilenameLintConfig {
+      ls: IndexMap::from([
+        (".ts".to_string(), vec![FilenameCase::Kebab].into()),
+        (".d.ts".to_string(), vec![FilenameCase::Kebab].into()),
+        ("src/**/*.ts".to_string(), vec![FilenameCase::Camel].into()),
+      ]),
+      ..Default::default()
+    };
+    let ctx = LintContext::new(&config, &[]);
+    let keys = |path| ctx.scopes(path).iter().map(|scope| scope.key).collect::<Vec<_>>();
+    assert_eq!(keys("src/user.ts"), vec!["src/**/*.ts"]);
+    assert_eq!(keys("types/user.d.ts"), vec![".ts", ".d.ts"]);
+    assert!(keys("README.md").is_empty());
+  }
+}
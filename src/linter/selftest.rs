@@ -0,0 +1,113 @@
+use crate::config::FilenameLintConfig;
+use crate::linter::visitor::{glob_ext, is_glob};
+use crate::linter::{lint_file, Issue, RuleContext};
+use std::fmt::Display;
+
+/// A sample name from the config's `tests` that did not fare as declared.
+#[derive(Debug, PartialEq)]
+pub struct ConfigTestFailure {
+  /// The `ls` key the sample was declared under.
+  pub key: String,
+  pub name: String,
+  /// Whether the sample was listed as `valid`.
+  pub valid: bool,
+  /// What a sample declared valid was reported for.
+  pub issues: Vec<Issue>,
+}
+
+impl Display for ConfigTestFailure {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self.issues.first() {
+      _ if !self.valid => write!(f, "{}: {} was expected to fail but passed", self.key, self.name),
+      Some(issue) => write!(f, "{}: {} was expected to pass: {}", self.key, self.name, issue),
+      None => write!(f, "{}: {} has no rule to test against", self.key, self.name),
+    }
+  }
+}
+
+/// Lint every sample name of `config.tests` against the `ls` rule of its key and return those
+/// that pass when declared `invalid` or fail when declared `valid`.
+///
+/// A sample without the key's extension, e.g. `fooBar` under `.ts`, gets it appended; under a
+/// category, the first extension of the category is used. Samples under a glob key are taken as
+/// paths and checked whether or not the glob matches them.
+pub fn run_config_tests(config: &FilenameLintConfig) -> Vec<ConfigTestFailure> {
+  let mut failures = vec![];
+  for (key, test) in &config.tests {
+    let samples = test.valid.iter().map(|name| (name, true));
+    let samples = samples.chain(test.invalid.iter().map(|name| (name, false)));
+    let Some(rule) = config.ls.get(key) else {
+      let missing = |(name, valid): (&String, bool)| ConfigTestFailure {
+        key: key.clone(),
+        name: name.clone(),
+        valid,
+        issues: vec![],
+      };
+      failures.extend(samples.filter(|(_, valid)| *valid).map(missing));
+      continue;
+    };
+    let exts = match config.categories.get(key) {
+      Some(category) => category.clone(),
+      None if is_glob(key) => vec![glob_ext(key)],
+      None => vec![key.clone()],
+    };
+    for (name, valid) in samples {
+      let (path, ext) = match exts.iter().find(|ext| name.ends_with(ext.as_str())) {
+        Some(ext) => (name.clone(), ext.as_str()),
+        None => {
+          let ext = exts.first().map_or("", String::as_str);
+          (format!("{}{}", name, ext), ext)
+        }
+      };
+      let issues = lint_file(&path, &RuleContext::new(rule, ext));
+      if issues.is_empty() != valid {
+        failures.push(ConfigTestFailure { key: key.clone(), name: name.clone(), valid, issues });
+      }
+    }
+  }
+  failures
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn run(tests: &str) -> Vec<ConfigTestFailure> {
+    let config = format!(
+      r#"{{ "ls": {{ ".ts": "camelCase", "style": "kebab-case", "src/**/*.tsx": "PascalCase" }},
+        "categories": {{ "style": [".css", ".scss"] }}, "ignore": [], "tests": {} }}"#,
+      tests
+    );
+    run_config_tests(&serde_json::from_str(&config).unwrap())
+  }
+
+  #[test]
+  fn test_expectations_hold() {
+    let tests = r#"{
+      ".ts": { "valid": ["fooBar", "fooBar.ts"], "invalid": ["FooBar"] },
+      "style": { "valid": ["main-nav", "theme.scss"], "invalid": ["mainNav.scss"] },
+      "src/**/*.tsx": { "valid": ["src/Button.tsx"], "invalid": ["src/button_group"] }
+    }"#;
+    assert_eq!(run(tests), vec![]);
+  }
+
+  #[test]
+  fn test_wrong_expectations_fail() {
+    let tests = r#"{
+      ".ts": { "valid": ["FooBar"], "invalid": ["fooBar"] },
+      ".rs": { "valid": ["foo_bar"] }
+    }"#;
+    let failures = run(tests);
+    let found =
+      failures.iter().map(|f| (f.key.as_str(), f.name.as_str(), f.valid)).collect::<Vec<_>>();
+    assert_eq!(
+      found,
+      vec![(".ts", "FooBar", true), (".ts", "fooBar", false), (".rs", "foo_bar", true)]
+    );
+    assert_eq!(failures[1].to_string(), ".ts: fooBar was expected to fail but passed");
+    assert_eq!(failures[2].to_string(), ".rs: foo_bar has no rule to test against");
+    assert!(failures[0]
+      .to_string()
+      .starts_with(".ts: FooBar was expected to pass: Filename FooBar"));
+  }
+}
@@ -0,0 +1,47 @@
+use crate::linter::{extension_start, Issue, IssueKind};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Report files whose stem equals the name of a directory next to them, like `utils.ts` beside
+/// `utils/`, since an import of `./utils` could mean either.
+pub fn lint_shadowed_dirs(files: &[String]) -> Vec<Issue> {
+  let dirs = files
+    .iter()
+    .flat_map(|file| Path::new(file).ancestors().skip(1))
+    .filter_map(Path::to_str)
+    .filter(|dir| !dir.is_empty())
+    .collect::<HashSet<&str>>();
+  files
+    .iter()
+    .filter_map(|path| {
+      let (parent, basename) = match path.rsplit_once('/') {
+        Some((parent, basename)) => (Some(parent), basename),
+        None => (None, path.as_str()),
+      };
+      let stem = &basename[..extension_start(basename)?];
+      let dir = match parent {
+        Some(parent) => format!("{}/{}", parent, stem),
+        None => stem.to_string(),
+      };
+      if !dirs.contains(dir.as_str()) {
+        return None;
+      }
+      Some(Issue::new(IssueKind::ShadowedDirectory { dir: format!("{}/", dir) }, basename, path))
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_file_shadows_directory() {
+    let files =
+      ["src/utils.ts", "src/utils/date.ts", "src/date.ts", "src/api/utils.ts"].map(String::from);
+    let issues = lint_shadowed_dirs(&files);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].path, "src/utils.ts");
+    assert_eq!(issues[0].to_string(), "File src/utils.ts shadows the directory src/utils/");
+  }
+}
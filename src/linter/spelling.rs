@@ -0,0 +1,68 @@
+use crate::config::convert::tokenize;
+use crate::config::SpellingConfig;
+use crate::linter::{Issue, IssueKind};
+use crate::scan::pattern::Pattern;
+use std::collections::HashSet;
+use std::io;
+
+/// Read a wordlist: one word per line, compared without regard to case. Blank lines and lines
+/// starting with `#` are skipped.
+pub fn load_wordlist(content: &str) -> HashSet<String> {
+  content
+    .lines()
+    .map(str::trim)
+    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+    .map(str::to_lowercase)
+    .collect()
+}
+
+/// Split the name of every file matching `config.files`, without its last extension, into words
+/// and report each word made of letters that the wordlist lacks, e.g. `recieve` in `recieve.md`.
+pub fn lint_spelling(config: &SpellingConfig, files: &[String]) -> io::Result<Vec<Issue>> {
+  let words = load_wordlist(&std::fs::read_to_string(&config.wordlist)?);
+  let patterns = config
+    .files
+    .iter()
+    .map(|glob| {
+      Pattern::new(glob).map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))
+    })
+    .collect::<io::Result<Vec<_>>>()?;
+  let issues = files
+    .iter()
+    .filter(|path| patterns.is_empty() || patterns.iter().any(|pattern| pattern.matches(path)))
+    .flat_map(|path| {
+      let basename = path.rsplit('/').next().unwrap_or(path);
+      let stem =
+        basename.rfind('.').filter(|dot| *dot > 0).map_or(basename, |dot| &basename[..dot]);
+      tokenize(stem)
+        .into_iter()
+        .filter(|word| word.chars().all(char::is_alphabetic) && !words.contains(word))
+        .map(|word| Issue::new(IssueKind::UnknownWord { word }, basename, path))
+        .collect::<Vec<_>>()
+    })
+    .collect();
+  Ok(issues)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_unknown_words() {
+    let dir = tempfile::tempdir().unwrap();
+    let wordlist = dir.path().join("words.txt");
+    std::fs::write(&wordlist, "# known words\nreceive\nPayment\n\nguide\n").unwrap();
+    let config = SpellingConfig { wordlist, files: vec!["docs/**/*.md".to_string()] };
+    let files =
+      ["docs/receive-payment.md", "docs/guides/recieve.md", "src/recieve.ts", "docs/v2.md"]
+        .map(String::from);
+    let issues = lint_spelling(&config, &files).unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].path, "docs/guides/recieve.md");
+    assert_eq!(issues[0].kind, IssueKind::UnknownWord { word: "recieve".to_string() });
+
+    let missing = SpellingConfig { wordlist: dir.path().join("missing.txt"), files: vec![] };
+    assert!(lint_spelling(&missing, &files).is_err());
+  }
+}
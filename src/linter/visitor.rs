@@ -1,33 +1,259 @@
-use crate::config::{FilenameCase, FilenameLintConfig};
-use crate::linter::{lint_files, Issue};
-use std::sync::Arc;
+use crate::config::{FilenameLintConfig, Root, Severity, UnmatchedFiles, UserRegex};
+use crate::linter::extension::lint_allowed_extensions;
+use crate::linter::mirror::lint_test_files;
+use crate::linter::required::lint_required_files;
+use crate::linter::rule::{LintContext, Registry, RuleConflict, ScannedFile};
+use crate::linter::shadow::lint_shadowed_dirs;
+use crate::linter::spelling::lint_spelling;
+use crate::linter::{Issue, IssueKind};
+use crate::scan::path::normalize_path;
+use crate::scan::scanner::{filter_paths, ScanOptions};
+use indexmap::IndexMap;
+use std::cmp::Reverse;
+use std::fmt::Display;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
 
-pub fn lint_filenames(config: &FilenameLintConfig, file_list: &Vec<String>) -> Vec<Issue> {
+/// How much of a file is read when looking for the generated marker.
+const GENERATED_PREFIX_BYTES: u64 = 1024;
+
+pub(crate) fn is_generated(path: &Path, marker: &UserRegex) -> std::io::Result<bool> {
+  let mut prefix = vec![];
+  File::open(path)?.take(GENERATED_PREFIX_BYTES).read_to_end(&mut prefix)?;
+  Ok(marker.0.is_match(&String::from_utf8_lossy(&prefix)))
+}
+
+/// An `ls` key naming a path glob, such as `src/components/**/*.ts`, rather than an extension.
+pub(crate) fn is_glob(key: &str) -> bool {
+  key.contains(['/', '*', '?'])
+}
+
+/// The extension stripped from files matched by a glob key: everything from the first dot of
+/// its last segment, e.g. `.test.ts` for `src/**/*.test.ts`. Empty for a glob like `src/**`,
+/// whose files each lose their own extension.
+pub(crate) fn glob_ext(glob: &str) -> String {
+  let last = glob.rsplit('/').next().unwrap_or(glob);
+  last.find('.').map(|dot| last[dot..].to_string()).unwrap_or_default()
+}
+
+/// How specific a glob key is. More path segments win, then fewer wildcards, so
+/// `src/components/**/*.ts` beats `src/**/*.ts` for `src/components/button/index.ts`.
+pub(crate) fn specificity(glob: &str) -> (usize, Reverse<usize>) {
+  let segments = glob.split('/').filter(|segment| !segment.is_empty()).count();
+  let wildcards = glob.matches(['*', '?']).count();
+  (segments, Reverse(wildcards))
+}
+
+/// A file that could not be checked.
+#[derive(Debug)]
+pub struct LintError {
+  pub path: String,
+  pub error: std::io::Error,
+}
+
+impl Display for LintError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Cannot read {}: {}", self.path, self.error)
+  }
+}
+
+/// The outcome of a lint run: the issues found, and the files that could not be checked.
+#[derive(Debug, Default)]
+pub struct Report {
+  pub issues: Vec<Issue>,
+  pub errors: Vec<LintError>,
+  /// How many files each `ls` key was checked against, in declaration order.
+  pub matched: IndexMap<String, usize>,
+  /// The sets of rules that contradict each other for some files, each reported once.
+  pub conflicts: Vec<RuleConflict>,
+}
+
+impl Report {
+  /// The `ls` keys that matched no file, such as a `.coffee` rule left over after a migration.
+  pub fn unused_rules(&self) -> Vec<String> {
+    self.matched.iter().filter(|(_, count)| **count == 0).map(|(key, _)| key.clone()).collect()
+  }
+}
+
+/// The root a file belongs to, the longest one containing it, and the file's path within it.
+pub(crate) fn find_root<'a>(roots: &'a [Root], file: &'a str) -> Option<(usize, &'a str)> {
+  roots
+    .iter()
+    .enumerate()
+    .filter_map(|(index, root)| {
+      let dir = root.path.trim_start_matches("./").trim_end_matches('/');
+      Some((index, file.strip_prefix(dir)?.strip_prefix('/')?))
+    })
+    .min_by_key(|(_, relative)| relative.len())
+}
+
+/// Lint `file_list` against `config`.
+///
+/// The globs of `config` must compile, as they do in a loaded config. A glob that does not
+/// matches nothing here, so check one built in code with
+/// [`check_globs`](FilenameLintConfig::check_globs) first.
+pub fn lint_filenames(config: &FilenameLintConfig, file_list: &[String]) -> Report {
+  lint_with(&Registry::default(), config, file_list)
+}
+
+/// Like [`lint_filenames`], with the per-file rules of `registry` in place of the built-in ones.
+///
+/// Paths are normalized first, so a list scanned from `.` such as `./src/main.rs` is reported
+/// as `src/main.rs` by every check. Absolute paths are kept absolute.
+pub fn lint_with(registry: &Registry, config: &FilenameLintConfig, file_list: &[String]) -> Report {
+  let file_list = file_list.iter().map(|file| normalize_path(file)).collect::<Vec<_>>();
+  lint_tree(registry, config, &file_list, Path::new(""))
+}
+
+/// Lint the files of a tree at `base`, given relative to it, handing those under a root to the
+/// root's config.
+fn lint_tree(
+  registry: &Registry,
+  config: &FilenameLintConfig,
+  file_list: &[String],
+  base: &Path,
+) -> Report {
+  let mut scoped = vec![vec![]; config.roots.len()];
+  let mut outside = vec![];
+  for file in file_list {
+    match find_root(&config.roots, file) {
+      Some((index, relative)) => scoped[index].push(relative.to_string()),
+      None => outside.push(file.clone()),
+    }
+  }
+  let mut report = lint_scope(registry, config, &outside, base);
+  for (root, files) in config.roots.iter().zip(scoped) {
+    let (ignore, include) = (&root.config.ignore, &root.config.include);
+    let options = ScanOptions { hidden: true, ..ScanOptions::default() };
+    let files = match filter_paths(Path::new("."), files, ignore, include, &options) {
+      Ok(files) => files,
+      Err(error) => {
+        let error = std::io::Error::new(std::io::ErrorKind::InvalidInput, error);
+        report.errors.push(LintError { path: root.path.clone(), error });
+        continue;
+      }
+    };
+    let dir = root.path.trim_start_matches("./").trim_end_matches('/');
+    let nested = lint_tree(registry, &root.config, &files, &base.join(dir));
+    report.issues.extend(nested.issues.into_iter().map(|issue| issue.under(dir)));
+    report.errors.extend(nested.errors);
+    let matched =
+      nested.matched.into_iter().map(|(key, count)| (format!("{}: {}", dir, key), count));
+    report.matched.extend(matched);
+    report.conflicts.extend(nested.conflicts.into_iter().map(|conflict| RuleConflict {
+      path: format!("{}/{}", dir, conflict.path),
+      keys: conflict.keys.into_iter().map(|key| format!("{}: {}", dir, key)).collect(),
+      ..conflict
+    }));
+  }
+  report
+}
+
+/// The issues of a single file that do not depend on the other files: those of the rules of
+/// `registry`, and under `unmatchedFiles` whether any `ls` rule applies.
+pub(crate) fn lint_each(registry: &Registry, ctx: &LintContext, file: &str) -> Vec<Issue> {
+  let mut issues = vec![];
+  let severity = match ctx.config.unmatched_files {
+    UnmatchedFiles::Ignore => None,
+    UnmatchedFiles::Warn => Some(Severity::Warning),
+    UnmatchedFiles::Error => Some(Severity::Error),
+  };
+  if let Some(severity) = severity.filter(|_| ctx.scopes(file).is_empty()) {
+    let basename = ScannedFile::new(file).basename;
+    let issue = Issue { severity, ..Issue::new(IssueKind::Unmatched, basename, file) };
+    issues.push(issue.with_rule("unmatchedFiles"));
+  }
+  issues.extend(registry.check(&ScannedFile::new(file), ctx));
+  issues
+}
+
+/// Lint files that all fall under the rules of `config` itself.
+fn lint_scope(
+  registry: &Registry,
+  config: &FilenameLintConfig,
+  file_list: &[String],
+  base: &Path,
+) -> Report {
+  let mut errors = vec![];
+  let file_list = match &config.generated_marker {
+    Some(marker) => file_list
+      .iter()
+      .filter(|file| match is_generated(&base.join(file), marker) {
+        Ok(generated) => !generated,
+        Err(error) => {
+          let path = base.join(file).to_str().unwrap().to_string();
+          errors.push(LintError { path, error });
+          false
+        }
+      })
+      .cloned()
+      .collect::<Vec<String>>(),
+    None => file_list.to_vec(),
+  };
+  let file_list = &file_list;
   let mut result = vec![];
-  config.ls.iter().for_each(|(ext, patterns)| {
-    let patterns: Arc<Vec<FilenameCase>> = Arc::new(patterns.to_vec());
-    let files = file_list.iter().filter(|file| file.ends_with(ext)).cloned().collect();
-    let issues = lint_files(files, ext.to_string(), &patterns);
-    issues.into_iter().for_each(|issue| result.push(issue));
-  });
-  result
+  let mut matched = config.ls.keys().map(|key| (key.clone(), 0)).collect::<IndexMap<_, _>>();
+  let mut conflicts = IndexMap::<Vec<String>, RuleConflict>::new();
+
+  let ctx = LintContext::new(config, file_list);
+  for file in file_list {
+    ctx.scopes(file).iter().for_each(|scope| matched[scope.key] += 1);
+    if let Some(conflict) = ctx.conflict(file) {
+      conflicts.entry(conflict.keys.clone()).and_modify(|seen| seen.files += 1).or_insert(conflict);
+    }
+    result.extend(lint_each(registry, &ctx, file));
+  }
+  if let Some(test_files) = &config.test_files {
+    let issues = lint_test_files(test_files, file_list);
+    result.extend(issues.into_iter().map(|issue| issue.with_rule("testFiles")));
+  }
+  if config.no_shadowed_dirs {
+    let issues = lint_shadowed_dirs(file_list);
+    result.extend(issues.into_iter().map(|issue| issue.with_rule("noShadowedDirs")));
+  }
+  if !config.required_files.is_empty() {
+    let issues = lint_required_files(&config.required_files, file_list);
+    result.extend(issues.into_iter().map(|issue| issue.with_rule("requiredFiles")));
+  }
+  if !config.allowed_extensions.is_empty() {
+    let issues = lint_allowed_extensions(&config.allowed_extensions, file_list);
+    result.extend(issues.into_iter().map(|issue| issue.with_rule("allowedExtensions")));
+  }
+  if let Some(spelling) = &config.spelling {
+    match lint_spelling(spelling, file_list) {
+      Ok(issues) => result.extend(issues.into_iter().map(|issue| issue.with_rule("spelling"))),
+      Err(error) => errors.push(LintError { path: spelling.wordlist.display().to_string(), error }),
+    }
+  }
+  #[cfg(feature = "imports")]
+  if config.import_case {
+    let (issues, unread) = crate::linter::imports::lint_import_case(file_list, base);
+    result.extend(issues.into_iter().map(|issue| issue.with_rule("importCase")));
+    errors.extend(unread);
+  }
+  let conflicts = conflicts.into_values().collect();
+  Report { issues: result, errors, matched, conflicts }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::config::FilenameCase;
+  use crate::config::{FilenameCase, TestFileConfig};
+  use indexmap::IndexMap;
   use std::collections::HashMap;
+  use std::sync::Arc;
 
   #[test]
   fn test_lint_filenames() {
     let config = Arc::new(FilenameLintConfig {
       ls: {
-        let mut map = HashMap::new();
-        map.insert(".rs".to_string(), vec![FilenameCase::Snake]);
+        let mut map = IndexMap::new();
+        map.insert(".rs".to_string(), vec![FilenameCase::Snake].into());
         map
       },
       ignore: vec![],
+      ..Default::default()
     });
     let files = vec![
       "src/main.rs".to_string(),
@@ -35,7 +261,208 @@ mod tests {
       "src/linter/hello-world.rs".to_string(),
       "src/linter/HELLO_WORLD.rs".to_string(),
     ];
-    let issues = lint_filenames(&config, &files);
+    let issues = lint_filenames(&config, &files).issues;
     assert_eq!(issues.len(), 2);
   }
+
+  #[test]
+  fn test_unmatched_files() {
+    let lint = |unmatched_files| {
+      let config = FilenameLintConfig {
+        ls: IndexMap::from([(".ts".to_string(), vec![FilenameCase::Kebab].into())]),
+        unmatched_files,
+        ..Default::default()
+      };
+      let files = ["src/user-service.ts".to_string(), "src/Logo.svg".to_string()];
+      lint_filenames(&config, &files).issues
+    };
+    assert!(lint(UnmatchedFiles::Ignore).is_empty());
+    for (mode, severity) in
+      [(UnmatchedFiles::Warn, Severity::Warning), (UnmatchedFiles::Error, Severity::Error)]
+    {
+      let issues = lint(mode);
+      assert_eq!(issues.len(), 1);
+      assert_eq!(issues[0].kind, IssueKind::Unmatched);
+      assert_eq!(issues[0].path, "src/Logo.svg");
+      assert_eq!(issues[0].rule, "unmatchedFiles");
+      assert_eq!(issues[0].severity, severity);
+    }
+    assert_eq!(FilenameLintConfig::default().unmatched_files, UnmatchedFiles::Ignore);
+  }
+
+  #[test]
+  fn test_no_dot_prefix() {
+    let config = FilenameLintConfig {
+      ls: IndexMap::from([(".ts".to_string(), vec![FilenameCase::Kebab].into())]),
+      test_files: Some(TestFileConfig { suffixes: vec![".test".to_string()] }),
+      unmatched_files: UnmatchedFiles::Error,
+      ..Default::default()
+    };
+    let files = ["./src/UserService.ts", "./src/order.test.ts", "./logo.svg", "/abs/Ok.ts"];
+    let issues = lint_filenames(&config, &files.map(String::from)).issues;
+    let mut paths = issues.iter().map(|issue| issue.path.as_str()).collect::<Vec<_>>();
+    paths.sort();
+    let expected = ["/abs/Ok.ts", "logo.svg", "src/UserService.ts", "src/order.test.ts"];
+    // `order.test.ts` is both mis-cased and an orphan test
+    assert_eq!(paths, [&expected[..], &expected[3..]].concat());
+  }
+
+  #[test]
+  fn test_unused_rules() {
+    let config = FilenameLintConfig {
+      ls: IndexMap::from([
+        (".ts".to_string(), vec![FilenameCase::Kebab].into()),
+        (".coffee".to_string(), vec![FilenameCase::Kebab].into()),
+        ("legacy/**".to_string(), vec![FilenameCase::Snake].into()),
+      ]),
+      ..Default::default()
+    };
+    let files = ["src/user-service.ts".to_string(), "src/order.ts".to_string()];
+    let report = lint_filenames(&config, &files);
+    assert_eq!(report.matched[".ts"], 2);
+    assert_eq!(report.unused_rules(), vec![".coffee", "legacy/**"]);
+  }
+
+  #[test]
+  fn test_roots() {
+    let root = |path: &str, case: FilenameCase| Root {
+      path: path.to_string(),
+      config: FilenameLintConfig {
+        ls: IndexMap::from([(".ts".to_string(), vec![case].into())]),
+        ..Default::default()
+      },
+    };
+    let config = FilenameLintConfig {
+      ls: IndexMap::from([(".ts".to_string(), vec![FilenameCase::Camel].into())]),
+      roots: vec![
+        root("packages/web", FilenameCase::Kebab),
+        root("packages/api/", FilenameCase::Snake),
+      ],
+      ..Default::default()
+    };
+    let files = [
+      "packages/web/user-service.ts",
+      "packages/web/user_service.ts",
+      "packages/api/user-service.ts",
+      "packages/api/user_service.ts",
+      "packages/other/userService.ts",
+      "scripts/user-service.ts",
+    ];
+    let report = lint_filenames(&config, &files.map(String::from));
+    let paths = report.issues.iter().map(|issue| issue.path.as_str()).collect::<Vec<_>>();
+    assert_eq!(
+      paths,
+      vec![
+        "scripts/user-service.ts",
+        "packages/web/user_service.ts",
+        "packages/api/user-service.ts"
+      ]
+    );
+    assert_eq!(report.matched["packages/web: .ts"], 2);
+    assert_eq!(report.matched[".ts"], 2);
+  }
+
+  #[test]
+  fn test_skip_generated_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let generated = dir.path().join("BadName.ts");
+    std::fs::create_dir(dir.path().join("src")).unwrap();
+    let handwritten = dir.path().join("src/BadName.ts");
+    std::fs::write(&generated, "// @generated by protoc\nexport {};\n").unwrap();
+    std::fs::write(&handwritten, "export {};\n").unwrap();
+    let config = FilenameLintConfig {
+      ls: IndexMap::from([(".ts".to_string(), vec![FilenameCase::Kebab].into())]),
+      generated_marker: Some(UserRegex(regex::Regex::new("@generated").unwrap())),
+      ..Default::default()
+    };
+    let files = [generated, handwritten].map(|file| file.to_str().unwrap().to_string());
+    let issues = lint_filenames(&config, &files).issues;
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].path, files[1]);
+  }
+
+  #[test]
+  fn test_category_rule() {
+    let config = FilenameLintConfig {
+      ls: IndexMap::from([("style".to_string(), vec![FilenameCase::Kebab].into())]),
+      categories: HashMap::from([(
+        "style".to_string(),
+        vec![".css".to_string(), ".scss".to_string(), ".less".to_string()],
+      )]),
+      ..Default::default()
+    };
+    let files = ["src/main-page.scss", "src/mainPage.scss", "src/theme.less", "src/mainPage.ts"]
+      .map(String::from);
+    let issues = lint_filenames(&config, &files).issues;
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].path, "src/mainPage.scss");
+    assert_eq!(issues[0].filename, "mainPage");
+  }
+
+  #[test]
+  fn test_unreadable_file_is_reported() {
+    let dir = tempfile::tempdir().unwrap();
+    let missing = dir.path().join("BadName.ts").to_str().unwrap().to_string();
+    let config = FilenameLintConfig {
+      ls: IndexMap::from([(".ts".to_string(), vec![FilenameCase::Kebab].into())]),
+      generated_marker: Some(UserRegex(regex::Regex::new("@generated").unwrap())),
+      ..Default::default()
+    };
+    let report = lint_filenames(&config, &[missing.clone()]);
+    assert!(report.issues.is_empty());
+    assert_eq!(report.errors.len(), 1);
+    assert_eq!(report.errors[0].path, missing);
+    assert_eq!(report.errors[0].error.kind(), std::io::ErrorKind::NotFound);
+  }
+
+  #[test]
+  fn test_most_specific_glob_wins() {
+    let config = FilenameLintConfig {
+      ls: IndexMap::from([
+        ("src/**/*.ts".to_string(), vec![FilenameCase::Kebab].into()),
+        ("src/components/**/*.ts".to_string(), vec![FilenameCase::Pascal].into()),
+        (".ts".to_string(), vec![FilenameCase::Snake].into()),
+      ]),
+      ..Default::default()
+    };
+    let files = [
+      "src/components/button/PrimaryButton.ts",
+      "src/components/button/primary-button.ts",
+      "src/utils/date-format.ts",
+      "src/utils/DateFormat.ts",
+      "scripts/build_docs.ts",
+    ]
+    .map(String::from);
+    let mut issues = lint_filenames(&config, &files).issues;
+    issues.sort_by(|a, b| a.path.cmp(&b.path));
+    let paths = issues.iter().map(|issue| issue.path.as_str()).collect::<Vec<_>>();
+    assert_eq!(paths, vec!["src/components/button/primary-button.ts", "src/utils/DateFormat.ts"]);
+    assert_eq!(*issues[0].target, vec![FilenameCase::Pascal]);
+  }
+
+  #[test]
+  fn test_glob_specificity() {
+    assert!(specificity("src/components/**/*.ts") > specificity("src/**/*.ts"));
+    assert!(specificity("src/*/index.ts") > specificity("src/*/*.ts"));
+    assert_eq!(glob_ext("src/**/*.test.ts"), ".test.ts");
+    assert_eq!(glob_ext("src/**"), "");
+  }
+
+  #[test]
+  fn test_equal_specificity_first_declared_wins() {
+    let config: FilenameLintConfig = serde_json::from_str(
+      r#"{
+        "ls": {
+          "src/*/*.ts": ["PascalCase"],
+          "src/a*/*.ts": ["kebab-case"]
+        },
+        "ignore": []
+      }"#,
+    )
+    .unwrap();
+    assert_eq!(specificity("src/*/*.ts"), specificity("src/a*/*.ts"));
+    let issues = lint_filenames(&config, &["src/api/user-client.ts".to_string()]).issues;
+    assert_eq!(issues.len(), 1);
+    assert_eq!(*issues[0].target, vec![FilenameCase::Pascal]);
+  }
 }
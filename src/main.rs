@@ -1,17 +1,296 @@
-use crate::config::FilenameLintConfig;
-use crate::linter::visitor::lint_filenames;
-use crate::scan::scanner::scan_dir;
-use anyhow::Result;
-
-mod config;
-mod linter;
-mod scan;
-
-fn main() -> Result<()> {
-  let config = FilenameLintConfig::load_file()?;
-  let files = scan_dir(".", &config.ignore);
-  lint_filenames(&config, &files).iter().for_each(|issue| {
-    println!("{}", issue);
-  });
+use crate::cli::{Cli, Command, Format};
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use fnlint::config::doctor::{diagnose, Level};
+use fnlint::config::init::{suggest_config, DEFAULT_IGNORE};
+use fnlint::config::migrate::migrate_file;
+use fnlint::config::schema::config_schema;
+use fnlint::config::FilenameLintConfig;
+use fnlint::fix::{apply_renames, plan_case_renames, plan_renames, RenameScript};
+use fnlint::linter::selftest::run_config_tests;
+use fnlint::linter::visitor::lint_filenames;
+use fnlint::linter::Issue;
+use fnlint::output::baseline::Baseline;
+use fnlint::output::merge::merge_report_files;
+use fnlint::output::{
+  Checkstyle, CodeClimate, DirsSummary, Json, Ndjson, PrettyJson, Stats, Summary, Table, TeamCity,
+  Text,
+};
+use fnlint::scan::git::{git_root, rev_files, staged_files};
+use fnlint::scan::manifest::read_manifest;
+use fnlint::scan::path::styled_path;
+use fnlint::scan::scanner::{filter_paths, scan, scan_dir, Scan, ScanOptions};
+use fnlint::watch::{watch_step, Watcher, POLL_INTERVAL};
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+mod cli;
+
+/// The exit code of a run that found issues, files it could not check, or with
+/// `--error-on-unused-rules` rules that matched nothing; also of failing `--config-test` samples.
+const ISSUES: i32 = 1;
+/// The exit code when the config cannot be found or loaded, `doctor` finds errors in it, or the
+/// command line asks for something impossible. Clap exits with it on invalid arguments too.
+const CONFIG_ERROR: i32 = 2;
+/// The exit code of a run whose scan hit `--timeout`, whatever the partial results were.
+const TIMED_OUT: i32 = 3;
+/// The exit code of any other error, e.g. a scan root that cannot be read, a failing `git`, or
+/// a `--changed-manifest` that does not exist.
+const FAILED: i32 = 4;
+
+/// Marks an error of the config or the command line, which exits with [`CONFIG_ERROR`] rather
+/// than [`FAILED`].
+#[derive(Debug)]
+struct ConfigError(String);
+
+impl Display for ConfigError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// The files given on the command line instead of a scan, if any.
+fn input_paths(cli: &Cli) -> Result<Option<Vec<String>>> {
+  #[cfg(feature = "archive")]
+  if let Some(archive) = &cli.archive {
+    return Ok(Some(fnlint::scan::archive::archive_entries(archive)?));
+  }
+  if cli.staged {
+    return Ok(Some(staged_files()?));
+  }
+  if let Some(rev) = &cli.rev {
+    return Ok(Some(rev_files(rev)?));
+  }
+  if let Some(manifest) = &cli.changed_manifest {
+    let manifest = read_manifest(manifest, Path::new("."))?;
+    for path in &manifest.missing {
+      eprintln!("Note: skipping {} from the manifest, which is not a file", path);
+    }
+    return Ok(Some(manifest.files));
+  }
+  if cli.stdin_paths {
+    let paths = std::io::stdin().lines().collect::<std::io::Result<Vec<String>>>()?;
+    return Ok(Some(paths.into_iter().filter(|path| !path.trim().is_empty()).collect()));
+  }
+  match cli.files.is_empty() {
+    true => Ok(None),
+    false => Ok(Some(cli.files.clone())),
+  }
+}
+
+fn main() {
+  if let Err(error) = run() {
+    eprintln!("Error: {:?}", error);
+    std::process::exit(match error.downcast_ref::<ConfigError>() {
+      Some(_) => CONFIG_ERROR,
+      None => FAILED,
+    });
+  }
+}
+
+fn run() -> Result<()> {
+  let cli = Cli::parse();
+  if cli.format == Format::Diff && !cli.fix && cli.autofix_case.is_none() {
+    bail!(ConfigError("--format diff requires --fix or --autofix-case".to_string()));
+  }
+  let root = ".";
+  if !cli.merge_output.is_empty() {
+    let merged = merge_report_files(&cli.merge_output)?;
+    match cli.pretty {
+      true => println!("{:#}", merged),
+      false => println!("{}", merged),
+    }
+    return Ok(());
+  }
+  if cli.config_schema {
+    println!("{}", serde_json::to_string_pretty(&config_schema())?);
+    return Ok(());
+  }
+  if cli.init_from_repo {
+    let ignore = DEFAULT_IGNORE.map(String::from);
+    let files = scan_dir(root, &ignore, &[], &ScanOptions::default())?;
+    println!("{}", serde_json::to_string_pretty(&suggest_config(&files))?);
+    return Ok(());
+  }
+  let path = match cli.config.clone() {
+    Some(path) => path,
+    None => FilenameLintConfig::find_file()
+      .ok_or_else(|| ConfigError("No configuration file found".to_string()))?,
+  };
+  let invalid = || ConfigError(format!("Cannot load the configuration {}", path.display()));
+  if let Some(output) = &cli.config_migrate {
+    let migrated = serde_json::to_string_pretty(&migrate_file(&path).with_context(invalid)?)?;
+    match output.as_os_str() == "-" {
+      true => println!("{}", migrated),
+      false => std::fs::write(output, migrated + "\n")?,
+    }
+    return Ok(());
+  }
+  if let Some(Command::Doctor) = cli.command {
+    let findings = diagnose(&path);
+    findings.iter().for_each(|finding| println!("{}", finding));
+    if findings.is_empty() {
+      println!("{} looks healthy", path.display());
+    }
+    if findings.iter().any(|finding| finding.level == Level::Error) {
+      std::process::exit(CONFIG_ERROR);
+    }
+    return Ok(());
+  }
+  let (config, trace) = FilenameLintConfig::load_traced(&path).with_context(invalid)?;
+  if !config.has_rules() {
+    let message = format!("{} defines no `ls` rules, so no file is checked", path.display());
+    match cli.strict_config {
+      true => bail!(ConfigError(message)),
+      false => eprintln!("Warning: {}", message),
+    }
+  }
+  if cli.debug_config {
+    println!("{}", serde_json::to_string_pretty(&trace)?);
+    return Ok(());
+  }
+  if cli.config_test {
+    let failures = run_config_tests(&config);
+    failures.iter().for_each(|failure| println!("{}", failure));
+    if !failures.is_empty() {
+      std::process::exit(ISSUES);
+    }
+    let count =
+      config.tests.values().map(|test| test.valid.len() + test.invalid.len()).sum::<usize>();
+    println!("All {} config tests passed", count);
+    return Ok(());
+  }
+  let mut config = config;
+  if let Some(case) = cli.enforce {
+    config.enforce(case);
+  }
+  // archive entries only exist inside the archive, so there is no content to sniff
+  #[cfg(feature = "archive")]
+  let config = match cli.archive {
+    Some(_) => FilenameLintConfig { generated_marker: None, ..config },
+    None => config,
+  };
+  // neither are the files of another revision in the working tree
+  let config = match cli.rev {
+    Some(_) => FilenameLintConfig { generated_marker: None, ..config },
+    None => config,
+  };
+  let options = ScanOptions {
+    hidden: cli.hidden,
+    path_filter: cli.path_filter.clone(),
+    timeout: cli.timeout.map(Duration::from_secs),
+    max_path_length: Some(cli.max_path_length),
+    follow_symlinks: config.follow_symlinks,
+    warn_external_symlinks: config.warn_external_symlinks,
+  };
+  if cli.watch {
+    let mut watcher = Watcher::new(root, &config, options)?;
+    loop {
+      std::thread::sleep(POLL_INTERVAL);
+      let (issues, renamed) = watch_step(&mut watcher, &config, cli.fix)?;
+      renamed.iter().for_each(|rename| println!("{}", rename));
+      print!("{}", Text(&issues));
+    }
+  }
+  let scan = match input_paths(&cli)? {
+    Some(paths) => {
+      let files = filter_paths(Path::new(root), paths, &config.ignore, &config.include, &options)?;
+      Scan { files, ..Scan::default() }
+    }
+    None => scan(root, &config.ignore, &config.include, &options)?,
+  };
+  scan.warnings.iter().for_each(|warning| eprintln!("Warning: {}", warning));
+  if scan.timed_out {
+    eprintln!("Warning: scan timed out, results incomplete");
+  }
+  let files = scan.files;
+  if let Some(case) = cli.autofix_case {
+    let renames = plan_case_renames(&files, case, &config.known_acronyms);
+    match cli.format {
+      Format::Diff => print!("{}", RenameScript(&renames)),
+      _ => apply_renames(renames)?.iter().for_each(|rename| println!("{}", rename)),
+    }
+    return Ok(());
+  }
+  let relative = match cli.git_relative {
+    true => git_root(Path::new(root)),
+    false => cli.relative_to,
+  };
+  let relative = relative.unwrap_or_else(|| PathBuf::from(root));
+  let report = lint_filenames(&config, &files);
+  report.errors.iter().for_each(|error| eprintln!("{}", error));
+  report.conflicts.iter().for_each(|conflict| eprintln!("Warning: {}", conflict));
+  let unused = match cli.error_on_unused_rules {
+    true => report.unused_rules(),
+    false => vec![],
+  };
+  unused.iter().for_each(|key| eprintln!("Rule {} matched no files", key));
+  let mut issues = report.issues;
+  if let Some(path) = &cli.write_baseline {
+    let baseline = Baseline::new(&issues);
+    baseline.write(path)?;
+    println!("Recorded {} issues in {}", baseline.len(), path.display());
+    return Ok(());
+  }
+  if let Some(path) = &cli.baseline {
+    issues = Baseline::read(path)?.new_issues(issues);
+  }
+  if cli.fix {
+    let renames = plan_renames(&issues, config.normalize_extension_case);
+    if cli.format == Format::Diff {
+      print!("{}", RenameScript(&renames));
+      return Ok(());
+    }
+    let renamed = apply_renames(renames)?;
+    renamed.iter().for_each(|rename| match cli.format {
+      Format::Json
+      | Format::PrettyJson
+      | Format::Ndjson
+      | Format::Codeclimate
+      | Format::Checkstyle
+      | Format::Teamcity
+      | Format::SummaryOnly => {
+        eprintln!("{}", rename)
+      }
+      _ => println!("{}", rename),
+    });
+    issues.retain(|issue| !renamed.iter().any(|rename| rename.from == issue.path));
+  }
+  let style = cli.path_style.unwrap_or(config.path_style);
+  let shown = issues
+    .iter()
+    .map(|issue| Issue { path: styled_path(&issue.path, style, &relative), ..issue.clone() })
+    .collect::<Vec<_>>();
+  match cli.format {
+    _ if cli.stats => print!("{}", Stats(&shown)),
+    _ if cli.dirs_summary => print!("{}", DirsSummary(&shown)),
+    Format::Json => print!("{}", Json { issues: &shown, pretty: cli.pretty }),
+    Format::PrettyJson => print!("{}", PrettyJson(&shown)),
+    Format::Ndjson => print!("{}", Ndjson(&shown)),
+    Format::Codeclimate => print!("{}", CodeClimate(&shown)),
+    Format::Checkstyle => print!("{}", Checkstyle(&shown)),
+    Format::Teamcity => print!("{}", TeamCity(&shown)),
+    Format::SummaryOnly => print!("{}", Summary(&shown)),
+    Format::Table if !issues.is_empty() => print!("{}", Table(&shown)),
+    _ => print!("{}", Text(&shown)),
+  }
+  #[cfg(feature = "github-review")]
+  if cli.github_review {
+    use fnlint::output::github::{post_review, Curl, PullRequest};
+    use fnlint::scan::path::relative_to;
+    let repository = git_root(Path::new(root)).unwrap_or_else(|| PathBuf::from(root));
+    let mut comments = issues.clone();
+    comments.iter_mut().for_each(|issue| issue.path = relative_to(&issue.path, &repository));
+    post_review(&Curl, &PullRequest::from_env()?, &comments)?;
+  }
+  if scan.timed_out {
+    std::process::exit(TIMED_OUT);
+  }
+  if !issues.is_empty() || !report.errors.is_empty() || !unused.is_empty() {
+    std::process::exit(ISSUES);
+  }
   Ok(())
 }
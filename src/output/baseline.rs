@@ -0,0 +1,156 @@
+use crate::linter::Issue;
+use crate::output::fingerprint;
+use anyhow::{bail, Context, Result};
+use std::path::Path;
+
+/// The first bytes of every baseline file.
+pub const MAGIC: &[u8; 4] = b"FNLB";
+/// The layout version written after [`MAGIC`]. A reader refuses versions it does not know, so a
+/// baseline written by a newer fnlint fails loudly instead of suppressing the wrong issues.
+pub const VERSION: u32 = 1;
+
+/// The issues accepted as they are, e.g. when adopting fnlint in a large codebase, so that only
+/// new ones are reported.
+///
+/// On disk a baseline is [`MAGIC`], the [`VERSION`] as a little-endian `u32`, the number of
+/// entries as a little-endian `u64`, and then that many [`fingerprint`]s as little-endian `u64`s
+/// in ascending order without duplicates. Thousands of issues take a few kilobytes, and loading
+/// needs no parsing beyond reading the numbers.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Baseline {
+  fingerprints: Vec<u64>,
+}
+
+impl Baseline {
+  pub fn new(issues: &[Issue]) -> Self {
+    let mut fingerprints = issues.iter().map(fingerprint).collect::<Vec<_>>();
+    fingerprints.sort_unstable();
+    fingerprints.dedup();
+    Baseline { fingerprints }
+  }
+
+  pub fn len(&self) -> usize {
+    self.fingerprints.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.fingerprints.is_empty()
+  }
+
+  pub fn contains(&self, issue: &Issue) -> bool {
+    self.fingerprints.binary_search(&fingerprint(issue)).is_ok()
+  }
+
+  /// The issues of `issues` the baseline does not list.
+  pub fn new_issues(&self, issues: Vec<Issue>) -> Vec<Issue> {
+    issues.into_iter().filter(|issue| !self.contains(issue)).collect()
+  }
+
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(16 + 8 * self.fingerprints.len());
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&VERSION.to_le_bytes());
+    bytes.extend_from_slice(&(self.fingerprints.len() as u64).to_le_bytes());
+    self.fingerprints.iter().for_each(|hash| bytes.extend_from_slice(&hash.to_le_bytes()));
+    bytes
+  }
+
+  pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+    let Some(rest) = bytes.strip_prefix(MAGIC.as_slice()) else {
+      bail!("Not an fnlint baseline");
+    };
+    let (version, rest) = rest.split_at_checked(4).context("Truncated baseline header")?;
+    let version = u32::from_le_bytes(version.try_into()?);
+    if version != VERSION {
+      bail!("Unsupported baseline version {}, this fnlint reads version {}", version, VERSION);
+    }
+    let (count, rest) = rest.split_at_checked(8).context("Truncated baseline header")?;
+    let count = u64::from_le_bytes(count.try_into()?);
+    if rest.len() as u64 != count.saturating_mul(8) {
+      bail!("The baseline lists {} entries but holds {} bytes of them", count, rest.len());
+    }
+    let fingerprints = rest
+      .chunks_exact(8)
+      .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+      .collect::<Vec<_>>();
+    if fingerprints.windows(2).any(|pair| pair[0] >= pair[1]) {
+      bail!("The baseline entries are not sorted");
+    }
+    Ok(Baseline { fingerprints })
+  }
+
+  pub fn read(path: &Path) -> Result<Self> {
+    let bytes = std::fs::read(path).with_context(|| format!("Cannot read {}", path.display()))?;
+    Self::from_bytes(&bytes).with_context(|| format!("Cannot load the baseline {}", path.display()))
+  }
+
+  pub fn write(&self, path: &Path) -> Result<()> {
+    std::fs::write(path, self.to_bytes())
+      .with_context(|| format!("Cannot write {}", path.display()))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::config::{FilenameCase, FilenameRule};
+  use crate::linter::{lint_files, IssueKind};
+
+  fn issues(files: &[&str]) -> Vec<Issue> {
+    let rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    let issues =
+      lint_files(files.iter().map(|file| file.to_string()).collect(), ".ts".into(), &rule);
+    issues.into_iter().map(|issue| issue.with_rule(".ts")).collect()
+  }
+
+  #[test]
+  fn baseline_round_trip_suppresses_known_issues() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("fnlint.baseline");
+    let known = issues(&["src/UserService.ts", "src/OrderItem.ts", "src/UserService.ts"]);
+    Baseline::new(&known).write(&path).unwrap();
+
+    let baseline = Baseline::read(&path).unwrap();
+    assert_eq!(baseline.len(), 2);
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), 16 + 2 * 8);
+    let now = issues(&["src/UserService.ts", "src/OrderItem.ts", "src/CartItem.ts", "src/ok.ts"]);
+    let fresh = baseline.new_issues(now);
+    assert_eq!(
+      fresh.iter().map(|issue| issue.path.as_str()).collect::<Vec<_>>(),
+      ["src/CartItem.ts"]
+    );
+
+    let other_rule = known[0].clone().with_rule("src/**/*.ts");
+    assert!(!baseline.contains(&other_rule));
+    let other_kind = Issue { kind: IssueKind::OrphanTest, ..known[0].clone() };
+    assert!(!baseline.contains(&other_kind));
+  }
+
+  #[test]
+  fn baseline_reports_a_second_issue_of_a_kind() {
+    let word = |word: &str| {
+      let kind = IssueKind::UnknownWord { word: word.to_string() };
+      Issue::new(kind, "recieve_adress.md", "docs/recieve_adress.md").with_rule("spelling")
+    };
+    let baseline = Baseline::from_bytes(&Baseline::new(&[word("recieve")]).to_bytes()).unwrap();
+    let fresh = baseline.new_issues(vec![word("recieve"), word("adress")]);
+    assert_eq!(fresh, vec![word("adress")]);
+
+    let mut rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    rule.full_path = true;
+    let dirs = lint_files(vec!["A/B/x.ts".to_string()], ".ts".into(), &rule);
+    let baseline = Baseline::new(&dirs[..1]);
+    assert_eq!(baseline.new_issues(dirs.clone()), vec![dirs[1].clone()]);
+  }
+
+  #[test]
+  fn baseline_rejects_other_versions_and_damage() {
+    let mut bytes = Baseline::new(&issues(&["src/UserService.ts"])).to_bytes();
+    assert!(Baseline::from_bytes(&bytes).is_ok());
+    assert!(Baseline::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(Baseline::from_bytes(b"src/UserService.ts").is_err());
+    bytes[4] = 2;
+    let error = Baseline::from_bytes(&bytes).unwrap_err();
+    assert_eq!(error.to_string(), "Unsupported baseline version 2, this fnlint reads version 1");
+  }
+}
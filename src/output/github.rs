@@ -0,0 +1,185 @@
+use crate::linter::Issue;
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// The pull request a GitHub Actions run was triggered for.
+#[derive(Debug, PartialEq)]
+pub struct PullRequest {
+  /// `owner/name`, as in `GITHUB_REPOSITORY`.
+  pub repository: String,
+  pub number: u64,
+  /// The head commit, which review comments are attached to.
+  pub commit: String,
+  pub token: String,
+  pub api_url: String,
+}
+
+impl PullRequest {
+  /// Read the pull request from the environment of a GitHub Actions run: `GITHUB_TOKEN`,
+  /// `GITHUB_REPOSITORY`, and the `pull_request` of the event at `GITHUB_EVENT_PATH`.
+  pub fn from_env() -> Result<Self> {
+    Self::from_vars(|name| std::env::var(name).ok())
+  }
+
+  fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self> {
+    let required = |name| var(name).with_context(|| format!("{} is not set", name));
+    let token = required("GITHUB_TOKEN")?;
+    let repository = required("GITHUB_REPOSITORY")?;
+    let event_path = required("GITHUB_EVENT_PATH")?;
+    let event = std::fs::read_to_string(&event_path)
+      .with_context(|| format!("Cannot read the event at {}", event_path))?;
+    let event = serde_json::from_str::<Value>(&event)?;
+    let pull_request = &event["pull_request"];
+    let (Some(number), Some(commit)) =
+      (pull_request["number"].as_u64(), pull_request["head"]["sha"].as_str())
+    else {
+      bail!("--github-review needs a pull_request event");
+    };
+    let api_url = var("GITHUB_API_URL").unwrap_or_else(|| "https://api.github.com".to_string());
+    Ok(PullRequest { repository, number, commit: commit.to_string(), token, api_url })
+  }
+}
+
+/// Sends requests to the GitHub API.
+pub trait ApiClient {
+  fn post(&self, url: &str, token: &str, body: &Value) -> Result<()>;
+}
+
+/// Posts with `curl`, handing it the token and body on stdin so neither shows up in the process
+/// list.
+pub struct Curl;
+
+impl ApiClient for Curl {
+  fn post(&self, url: &str, token: &str, body: &Value) -> Result<()> {
+    let quote = |value: &str| format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""));
+    let config = [
+      format!("url = {}", quote(url)),
+      "request = \"POST\"".to_string(),
+      format!("header = {}", quote(&format!("Authorization: Bearer {}", token))),
+      "header = \"Accept: application/vnd.github+json\"".to_string(),
+      format!("data-binary = {}", quote(&body.to_string())),
+    ];
+    let mut child = Command::new("curl")
+      .args(["--silent", "--show-error", "--fail", "--output", "/dev/null", "--config", "-"])
+      .stdin(Stdio::piped())
+      .stderr(Stdio::piped())
+      .spawn()
+      .context("Cannot run curl")?;
+    child.stdin.take().unwrap().write_all(config.join("\n").as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+      bail!("POST {} failed: {}", url, String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+  }
+}
+
+/// The URL and body of a file-level review comment for each issue. Filenames have no line to
+/// point at, so the comment is attached to the file as a whole.
+pub fn review_comments(pr: &PullRequest, issues: &[Issue]) -> Vec<(String, Value)> {
+  let url = format!("{}/repos/{}/pulls/{}/comments", pr.api_url, pr.repository, pr.number);
+  issues
+    .iter()
+    .map(|issue| {
+      let body = match issue.suggestion() {
+        Some(suggestion) => format!("{}\n\nSuggested name: `{}`", issue, suggestion),
+        None => issue.to_string(),
+      };
+      let payload = json!({
+        "body": body,
+        "commit_id": pr.commit,
+        "path": issue.path,
+        "subject_type": "file",
+      });
+      (url.clone(), payload)
+    })
+    .collect()
+}
+
+/// Post a review comment for each issue, whose paths must be relative to the repository root.
+/// Every comment is attempted; the number that failed, e.g. for files outside the diff, is
+/// reported at the end.
+pub fn post_review(client: &impl ApiClient, pr: &PullRequest, issues: &[Issue]) -> Result<()> {
+  let mut failed = 0;
+  for (url, body) in review_comments(pr, issues) {
+    if let Err(error) = client.post(&url, &pr.token, &body) {
+      eprintln!("{:#}", error);
+      failed += 1;
+    }
+  }
+  if failed > 0 {
+    bail!("{} of {} review comments could not be posted", failed, issues.len());
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::config::{FilenameCase, FilenameRule};
+  use crate::linter::lint_files;
+  use std::cell::RefCell;
+
+  /// Records every request instead of sending it.
+  #[derive(Default)]
+  struct MockClient {
+    requests: RefCell<Vec<(String, String, Value)>>,
+  }
+
+  impl ApiClient for MockClient {
+    fn post(&self, url: &str, token: &str, body: &Value) -> Result<()> {
+      self.requests.borrow_mut().push((url.to_string(), token.to_string(), body.clone()));
+      Ok(())
+    }
+  }
+
+  fn pull_request() -> PullRequest {
+    PullRequest {
+      repository: "7086cmd/fnlint".to_string(),
+      number: 42,
+      commit: "abc123".to_string(),
+      token: "secret".to_string(),
+      api_url: "https://api.github.com".to_string(),
+    }
+  }
+
+  #[test]
+  fn test_review_payloads() {
+    let rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    let files = ["src/UserService.ts", "src/ok.ts"].map(String::from).to_vec();
+    let issues = lint_files(files, ".ts".to_string(), &rule);
+    let client = MockClient::default();
+    post_review(&client, &pull_request(), &issues).unwrap();
+    let requests = client.requests.into_inner();
+    assert_eq!(requests.len(), 1);
+    let (url, token, body) = &requests[0];
+    assert_eq!(url, "https://api.github.com/repos/7086cmd/fnlint/pulls/42/comments");
+    assert_eq!(token, "secret");
+    assert_eq!(body["path"], "src/UserService.ts");
+    assert_eq!(body["commit_id"], "abc123");
+    assert_eq!(body["subject_type"], "file");
+    assert!(body["body"].as_str().unwrap().ends_with("Suggested name: `user-service`"));
+  }
+
+  #[test]
+  fn test_pull_request_from_env() {
+    let dir = tempfile::tempdir().unwrap();
+    let event = dir.path().join("event.json");
+    let event_path = event.to_str().unwrap().to_string();
+    std::fs::write(&event, r#"{ "pull_request": { "number": 42, "head": { "sha": "abc123" } } }"#)
+      .unwrap();
+    let vars = |name: &str| match name {
+      "GITHUB_TOKEN" => Some("secret".to_string()),
+      "GITHUB_REPOSITORY" => Some("7086cmd/fnlint".to_string()),
+      "GITHUB_EVENT_PATH" => Some(event_path.clone()),
+      _ => None,
+    };
+    assert_eq!(PullRequest::from_vars(vars).unwrap(), pull_request());
+
+    std::fs::write(&event, r#"{ "ref": "refs/heads/main" }"#).unwrap();
+    assert!(PullRequest::from_vars(vars).is_err());
+    assert!(PullRequest::from_vars(|_| None).is_err());
+  }
+}
@@ -0,0 +1,82 @@
+use crate::output::tool;
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Combine the documents of several `--format json` runs, e.g. one per subproject, into one.
+/// An issue reported by more than one run is kept once, and issues are sorted by path, then by
+/// kind and message, and then by the rest of the issue, so that copies are always adjacent.
+pub fn merge_reports(reports: &[Value]) -> Result<Value> {
+  let mut issues = vec![];
+  for (index, report) in reports.iter().enumerate() {
+    let Some(Value::Array(found)) = report.get("issues") else {
+      bail!("Report {} has no `issues` array", index + 1);
+    };
+    issues.extend(found.iter().cloned());
+  }
+  let key = |issue: &Value| {
+    let field = |name| issue.get(name).and_then(Value::as_str).unwrap_or_default().to_string();
+    (field("path"), field("kind"), field("message"), issue.to_string())
+  };
+  issues.sort_by_key(key);
+  issues.dedup();
+  let files = issues.iter().filter_map(|issue| issue.get("path")?.as_str()).collect::<HashSet<_>>();
+  Ok(json!({
+    "tool": tool(),
+    "issues": issues,
+    "summary": { "issues": issues.len(), "files": files.len() },
+  }))
+}
+
+/// Read the JSON reports at `paths` and [merge](merge_reports) them.
+pub fn merge_report_files(paths: &[impl AsRef<Path>]) -> Result<Value> {
+  let reports = paths
+    .iter()
+    .map(|path| {
+      let path = path.as_ref();
+      let content =
+        std::fs::read_to_string(path).with_context(|| format!("Cannot read {}", path.display()))?;
+      serde_json::from_str(&content)
+        .with_context(|| format!("{} is not a JSON report", path.display()))
+    })
+    .collect::<Result<Vec<Value>>>()?;
+  merge_reports(&reports)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::config::{FilenameCase, FilenameRule};
+  use crate::linter::lint_files;
+  use crate::output::Json;
+
+  #[test]
+  fn test_merge_reports() {
+    let rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    let report = |files: &[&str]| {
+      let files = files.iter().map(|file| file.to_string()).collect();
+      Json { issues: &lint_files(files, ".ts".to_string(), &rule), pretty: false }.to_string()
+    };
+    let dir = tempfile::tempdir().unwrap();
+    let (web, api) = (dir.path().join("web.json"), dir.path().join("api.json"));
+    std::fs::write(&web, report(&["web/Nav.ts", "shared/Date.ts", "web/ok.ts"])).unwrap();
+    std::fs::write(&api, report(&["shared/Date.ts", "api/User.ts"])).unwrap();
+
+    let merged = merge_report_files(&[web, api]).unwrap();
+    let paths = merged["issues"].as_array().unwrap().iter().map(|issue| &issue["path"]);
+    assert_eq!(paths.collect::<Vec<_>>(), vec!["api/User.ts", "shared/Date.ts", "web/Nav.ts"]);
+    assert_eq!(merged["summary"], json!({ "issues": 3, "files": 3 }));
+
+    let issue = |rule| json!({ "path": "a.ts", "kind": "case", "message": "m", "rule": rule });
+    let merged = merge_reports(&[
+      json!({ "issues": [issue(".ts"), issue("src/**")] }),
+      json!({ "issues": [issue(".ts")] }),
+    ])
+    .unwrap();
+    assert_eq!(merged["issues"], json!([issue(".ts"), issue("src/**")]));
+    assert_eq!(merged["summary"], json!({ "issues": 2, "files": 1 }));
+
+    assert!(merge_reports(&[json!({ "issues": [] }), json!([])]).is_err());
+  }
+}
@@ -0,0 +1,682 @@
+use crate::config::{FilenameCase, Severity};
+use crate::linter::{Issue, IssueKind};
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::fmt::Display;
+pub mod baseline;
+#[cfg(feature = "github-review")]
+pub mod github;
+pub mod merge;
+
+impl IssueKind {
+  /// A stable identifier for machine-readable output.
+  pub fn name(&self) -> &str {
+    match self {
+      IssueKind::Case => "case",
+      IssueKind::OrphanTest => "orphan-test",
+      IssueKind::MiscasedTest { .. } => "miscased-test",
+      IssueKind::DirectoryCase => "directory-case",
+      IssueKind::MissingExtension => "missing-extension",
+      IssueKind::ShadowedDirectory { .. } => "shadowed-directory",
+      IssueKind::MissingFile { .. } => "missing-file",
+      IssueKind::DisallowedExtension { .. } => "disallowed-extension",
+      IssueKind::Unmatched => "unmatched",
+      IssueKind::UnknownWord { .. } => "unknown-word",
+      IssueKind::InvalidLocale { .. } => "invalid-locale",
+      IssueKind::ImportCase { .. } => "import-case",
+      IssueKind::Custom { name, .. } => name,
+    }
+  }
+
+  /// The values the kind carries, e.g. the word of an unknown word, which together with the
+  /// file tell two issues of one kind apart.
+  pub fn details(&self) -> Vec<&str> {
+    match self {
+      IssueKind::MiscasedTest { source } => vec![source],
+      IssueKind::ShadowedDirectory { dir } => vec![dir],
+      IssueKind::MissingFile { file } => vec![file],
+      IssueKind::DisallowedExtension { ext } => vec![ext],
+      IssueKind::UnknownWord { word } => vec![word],
+      IssueKind::InvalidLocale { locale } => vec![locale],
+      IssueKind::ImportCase { specifier, file } => vec![specifier, file],
+      IssueKind::Custom { message, .. } => vec![message],
+      IssueKind::Case
+      | IssueKind::OrphanTest
+      | IssueKind::DirectoryCase
+      | IssueKind::MissingExtension
+      | IssueKind::Unmatched => vec![],
+    }
+  }
+}
+
+/// The JSON object describing a single issue.
+pub fn issue_json(issue: &Issue) -> Value {
+  json!({
+    "path": issue.path,
+    "filename": issue.filename,
+    "kind": issue.kind.name(),
+    "rule": issue.rule,
+    "severity": issue.severity.to_string(),
+    "expected": issue.target.iter().map(|case| case.to_string()).collect::<Vec<String>>(),
+    "suggestion": issue.suggestion(),
+    "fixable": issue.fixable(),
+    "message": issue.to_string(),
+  })
+}
+
+/// The name and version of the fnlint that wrote a report, heading the JSON documents so a
+/// report can be traced back to the release producing it.
+pub fn tool() -> Value {
+  json!({ "name": "fnlint", "version": env!("CARGO_PKG_VERSION") })
+}
+
+/// Renders one line per issue, marking those `--fix` resolves with `[fixable]`.
+pub struct Text<'a>(pub &'a [Issue]);
+
+impl Display for Text<'_> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    for issue in self.0 {
+      match issue.fixable() {
+        true => writeln!(f, "{} [fixable]", issue)?,
+        false => writeln!(f, "{}", issue)?,
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Renders the issues as a single JSON document, indented when `pretty` is set. A clean run
+/// still prints a document, with an empty `issues` array.
+pub struct Json<'a> {
+  pub issues: &'a [Issue],
+  pub pretty: bool,
+}
+
+impl Display for Json<'_> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let files = self.issues.iter().map(|issue| issue.path.as_str()).collect::<HashSet<&str>>();
+    let document = json!({
+      "tool": tool(),
+      "issues": self.issues.iter().map(issue_json).collect::<Vec<Value>>(),
+      "summary": { "issues": self.issues.len(), "files": files.len() },
+    });
+    match self.pretty {
+      true => writeln!(f, "{:#}", document),
+      false => writeln!(f, "{}", document),
+    }
+  }
+}
+
+/// Renders the issues as an indented JSON document that keeps to what is needed to act on
+/// each: the path, the case the name is in now, the expected cases and the suggested name,
+/// followed by a summary.
+pub struct PrettyJson<'a>(pub &'a [Issue]);
+
+impl Display for PrettyJson<'_> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let issues = self
+      .0
+      .iter()
+      .map(|issue| {
+        json!({
+          "path": issue.path,
+          "current": FilenameCase::detect(&issue.filename).map(|case| case.to_string()),
+          "expected": issue.target.iter().map(|case| case.to_string()).collect::<Vec<String>>(),
+          "suggestion": issue.suggestion(),
+          "fixable": issue.fixable(),
+        })
+      })
+      .collect::<Vec<Value>>();
+    let files = self.0.iter().map(|issue| issue.path.as_str()).collect::<HashSet<&str>>();
+    let fixable = self.0.iter().filter(|issue| issue.fixable()).count();
+    let document = json!({
+      "tool": tool(),
+      "issues": issues,
+      "summary": { "issues": self.0.len(), "files": files.len(), "fixable": fixable },
+    });
+    writeln!(f, "{:#}", document)
+  }
+}
+
+/// A hash of the issue's path, rule, kind, filename and the [`details`](IssueKind::details) of
+/// its kind that stays the same across runs and platforms, for tools tracking issues over time.
+/// Two issues differ in one of these, e.g. the two directories `fullPath` flags in `A/B/x.ts`.
+/// FNV-1a is used since std's hasher is randomly seeded.
+pub fn fingerprint(issue: &Issue) -> u64 {
+  let mut hash: u64 = 0xcbf29ce484222325;
+  let parts = [issue.path.as_str(), issue.rule.as_str(), issue.kind.name(), &issue.filename];
+  for part in parts.into_iter().chain(issue.kind.details()) {
+    for byte in part.bytes().chain([0]) {
+      hash ^= byte as u64;
+      hash = hash.wrapping_mul(0x100000001b3);
+    }
+  }
+  hash
+}
+
+/// Renders the issues as a Code Climate report, as read by GitLab CI.
+pub struct CodeClimate<'a>(pub &'a [Issue]);
+
+impl Display for CodeClimate<'_> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let issues = self
+      .0
+      .iter()
+      .map(|issue| {
+        let severity = match issue.severity {
+          Severity::Error => "major",
+          Severity::Warning => "minor",
+          Severity::Info => "info",
+        };
+        json!({
+          "type": "issue",
+          "engine_name": "fnlint",
+          "check_name": issue.kind.name(),
+          "description": issue.to_string(),
+          "categories": ["Style"],
+          "fingerprint": format!("{:016x}", fingerprint(issue)),
+          "severity": severity,
+          "location": { "path": issue.path, "lines": { "begin": 1 } },
+        })
+      })
+      .collect::<Vec<Value>>();
+    writeln!(f, "{}", Value::Array(issues))
+  }
+}
+
+/// Escape `value` for use in XML text and attribute values.
+fn xml_escape(value: &str) -> String {
+  value
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+    .replace('\'', "&apos;")
+}
+
+/// Renders the issues as a Checkstyle XML report, with one `<file>` per offending path.
+pub struct Checkstyle<'a>(pub &'a [Issue]);
+
+impl Display for Checkstyle<'_> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let mut files = IndexMap::<&str, Vec<&Issue>>::new();
+    self.0.iter().for_each(|issue| files.entry(&issue.path).or_default().push(issue));
+    writeln!(f, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(f, r#"<checkstyle version="4.3">"#)?;
+    for (path, issues) in files {
+      writeln!(f, r#"  <file name="{}">"#, xml_escape(path))?;
+      for issue in issues {
+        // an extension key such as `.ts` gives `fnlint.ts`, not `fnlint..ts`
+        let source = match issue.rule.is_empty() {
+          true => format!("fnlint.{}", issue.kind.name()),
+          false => format!("fnlint.{}", issue.rule.trim_start_matches('.')),
+        };
+        writeln!(
+          f,
+          r#"    <error line="1" severity="{}" message="{}" source="{}"/>"#,
+          issue.severity,
+          xml_escape(&issue.to_string()),
+          xml_escape(&source)
+        )?;
+      }
+      writeln!(f, "  </file>")?;
+    }
+    writeln!(f, "</checkstyle>")
+  }
+}
+
+/// Escape `value` for use in a TeamCity service message attribute.
+fn teamcity_escape(value: &str) -> String {
+  value.chars().fold(String::new(), |mut escaped, c| {
+    match c {
+      '|' | '\'' | '[' | ']' => escaped.extend(['|', c]),
+      '\n' => escaped.push_str("|n"),
+      '\r' => escaped.push_str("|r"),
+      '\u{0085}' => escaped.push_str("|x"),
+      '\u{2028}' => escaped.push_str("|l"),
+      '\u{2029}' => escaped.push_str("|p"),
+      c => escaped.push(c),
+    }
+    escaped
+  })
+}
+
+/// Renders the issues as TeamCity service messages: an `inspectionType` for each kind of issue
+/// found, declared before its first `inspection`, and an `inspection` per issue.
+pub struct TeamCity<'a>(pub &'a [Issue]);
+
+impl Display for TeamCity<'_> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let mut declared = HashSet::new();
+    for issue in self.0 {
+      let kind = issue.kind.name();
+      if declared.insert(kind) {
+        writeln!(
+          f,
+          "##teamcity[inspectionType id='fnlint.{}' name='{}' category='fnlint' description='{}']",
+          kind,
+          kind,
+          teamcity_escape(&format!("fnlint {} issues", kind))
+        )?;
+      }
+      let severity = match issue.severity {
+        Severity::Error => "ERROR",
+        Severity::Warning => "WARNING",
+        Severity::Info => "INFO",
+      };
+      writeln!(
+        f,
+        "##teamcity[inspection typeId='fnlint.{}' message='{}' file='{}' line='1' SEVERITY='{}']",
+        kind,
+        teamcity_escape(&issue.to_string()),
+        teamcity_escape(&issue.path),
+        severity
+      )?;
+    }
+    Ok(())
+  }
+}
+
+/// Renders the issues as newline-delimited JSON, one object per line; nothing for a clean run.
+pub struct Ndjson<'a>(pub &'a [Issue]);
+
+impl Display for Ndjson<'_> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    for issue in self.0 {
+      writeln!(f, "{}", issue_json(issue))?;
+    }
+    Ok(())
+  }
+}
+
+/// Renders the issues as an aligned table of path, detected case and expected cases, for reading
+/// in a terminal.
+pub struct Table<'a>(pub &'a [Issue]);
+
+impl Display for Table<'_> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let header = ["PATH".to_string(), "DETECTED".to_string(), "EXPECTED".to_string()];
+    let rows = self
+      .0
+      .iter()
+      .map(|issue| {
+        let detected = FilenameCase::detect(&issue.filename).map(|case| case.to_string());
+        let expected = issue.target.iter().map(|case| case.to_string()).collect::<Vec<String>>();
+        [issue.path.clone(), detected.unwrap_or_else(|| "-".to_string()), expected.join(", ")]
+      })
+      .collect::<Vec<[String; 3]>>();
+    let width = |column: usize| {
+      rows.iter().chain([&header]).map(|row| row[column].chars().count()).max().unwrap_or(0)
+    };
+    let (path_width, detected_width) = (width(0), width(1));
+    for row in [&header].into_iter().chain(&rows) {
+      writeln!(f, "{:path_width$}  {:detected_width$}  {}", row[0], row[1], row[2])?;
+    }
+    Ok(())
+  }
+}
+
+/// What an issue is grouped under in [`Stats`]: the singular and plural of its subject and verb,
+/// and the rest of the sentence.
+type Violation = ([&'static str; 2], [&'static str; 2], String);
+
+fn violation(issue: &Issue) -> Violation {
+  let expected = issue.target.iter().map(|case| case.to_string()).collect::<Vec<String>>();
+  let subject = match issue.kind {
+    IssueKind::DirectoryCase => ["directory", "directories"],
+    _ => ["file", "files"],
+  };
+  match &issue.kind {
+    IssueKind::Case | IssueKind::DirectoryCase if issue.filename.contains(char::is_whitespace) => {
+      (subject, ["contains", "contain"], "spaces".to_string())
+    }
+    IssueKind::Case | IssueKind::DirectoryCase => {
+      let should = format!("but should be {}", expected.join(" or "));
+      match FilenameCase::detect(&issue.filename) {
+        Some(case) => (subject, ["is", "are"], format!("{} {}", case, should)),
+        None => (subject, ["fits", "fit"], format!("no case {}", should)),
+      }
+    }
+    IssueKind::OrphanTest => (["test", "tests"], ["has", "have"], "no source file".to_string()),
+    IssueKind::MiscasedTest { .. } => {
+      (["test", "tests"], ["is", "are"], "cased unlike its source file".to_string())
+    }
+    IssueKind::MissingExtension => (subject, ["has", "have"], "no extension".to_string()),
+    IssueKind::ShadowedDirectory { .. } => {
+      (subject, ["is", "are"], "named like a sibling directory".to_string())
+    }
+    IssueKind::MissingFile { file } => {
+      (["directory", "directories"], ["lacks", "lack"], format!("a required {}", file))
+    }
+    IssueKind::DisallowedExtension { ext } => {
+      (subject, ["has", "have"], format!("the disallowed extension {}", ext))
+    }
+    IssueKind::Unmatched => (subject, ["has", "have"], "no rule".to_string()),
+    IssueKind::UnknownWord { word } => {
+      (subject, ["uses", "use"], format!("the unknown word {}", word))
+    }
+    IssueKind::Custom { name, .. } => (subject, ["breaks", "break"], format!("the rule {}", name)),
+    IssueKind::ImportCase { .. } => {
+      (subject, ["imports", "import"], "a path in the wrong case".to_string())
+    }
+    IssueKind::InvalidLocale { locale } => {
+      (subject, ["ends", "end"], format!("in the malformed locale {}", locale))
+    }
+  }
+}
+
+/// Counts the issues by what is wrong, most common first, such as `42 files are Pascal but
+/// should be kebab-case`. Patterns seen equally often keep the order they were first seen in.
+pub fn tally(issues: &[Issue]) -> Vec<(usize, String)> {
+  let mut counts = IndexMap::<Violation, usize>::new();
+  issues.iter().for_each(|issue| *counts.entry(violation(issue)).or_default() += 1);
+  let mut tally = counts
+    .into_iter()
+    .map(|((subject, verb, rest), count)| {
+      let plural = (count != 1) as usize;
+      (count, format!("{} {} {} {}", count, subject[plural], verb[plural], rest))
+    })
+    .collect::<Vec<_>>();
+  tally.sort_by_key(|(count, _)| std::cmp::Reverse(*count));
+  tally
+}
+
+/// Renders a single line counting the issues and the files they are in, e.g. `fnlint: 12 issues
+/// across 5 files`.
+pub struct Summary<'a>(pub &'a [Issue]);
+
+impl Display for Summary<'_> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let files = self.0.iter().map(|issue| issue.path.as_str()).collect::<HashSet<&str>>();
+    let plural = |count: usize, noun: &str| match count {
+      1 => format!("1 {}", noun),
+      count => format!("{} {}s", count, noun),
+    };
+    writeln!(f, "fnlint: {} across {}", plural(self.0.len(), "issue"), plural(files.len(), "file"))
+  }
+}
+
+/// Renders the [`tally`] of the issues, one pattern per line.
+pub struct Stats<'a>(pub &'a [Issue]);
+
+impl Display for Stats<'_> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    for (_, line) in tally(self.0) {
+      writeln!(f, "{}", line)?;
+    }
+    Ok(())
+  }
+}
+
+/// The directory an issue is counted under: the one it names for a missing file, and otherwise
+/// the one holding its file, with `./` for the top level.
+fn issue_dir(issue: &Issue) -> String {
+  match (&issue.kind, issue.path.rsplit_once('/')) {
+    (IssueKind::MissingFile { .. }, _) => format!("{}/", issue.path.trim_end_matches('/')),
+    (_, Some((dir, _))) => format!("{}/", dir),
+    (_, None) => "./".to_string(),
+  }
+}
+
+/// The directories with issues and how many each has, most first. Directories with equally many
+/// keep the order they were first seen in.
+pub fn dir_counts(issues: &[Issue]) -> Vec<(String, usize)> {
+  let mut counts = IndexMap::<String, usize>::new();
+  issues.iter().for_each(|issue| *counts.entry(issue_dir(issue)).or_default() += 1);
+  let mut counts = counts.into_iter().collect::<Vec<_>>();
+  counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+  counts
+}
+
+/// Renders the [`dir_counts`] of the issues, one `src/components/ (3 issues)` line per directory.
+pub struct DirsSummary<'a>(pub &'a [Issue]);
+
+impl Display for DirsSummary<'_> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    for (dir, count) in dir_counts(self.0) {
+      let noun = if count == 1 { "issue" } else { "issues" };
+      writeln!(f, "{} ({} {})", dir, count, noun)?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::config::{FilenameCase, FilenameRule};
+  use crate::linter::lint_files;
+
+  #[test]
+  fn test_dirs_summary_ranks_by_count() {
+    let rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    let files = [
+      "src/api/getUser.ts",
+      "src/components/UserCard.ts",
+      "src/components/ok-name.ts",
+      "src/components/OrderList.ts",
+      "BadRoot.ts",
+      "src/components/CartItem.ts",
+    ];
+    let mut issues = lint_files(files.map(String::from).to_vec(), ".ts".to_string(), &rule);
+    issues.push(Issue::new(IssueKind::MissingFile { file: "index.ts".into() }, "", "src/api"));
+    assert_eq!(
+      DirsSummary(&issues).to_string(),
+      "src/components/ (3 issues)\nsrc/api/ (2 issues)\n./ (1 issue)\n"
+    );
+    let counts = dir_counts(&issues[..4]);
+    assert_eq!(
+      counts,
+      vec![("src/components/".to_string(), 2), ("src/api/".to_string(), 1), ("./".to_string(), 1)]
+    );
+  }
+
+  #[test]
+  fn test_summary_line() {
+    let rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    let mut issues = lint_files(vec!["src/userService.ts".to_string()], ".ts".to_string(), &rule);
+    assert_eq!(Summary(&issues).to_string(), "fnlint: 1 issue across 1 file\n");
+    issues.push(Issue::new(IssueKind::Unmatched, "userService.ts", "src/userService.ts"));
+    issues.push(Issue::new(IssueKind::Unmatched, "Logo.svg", "src/Logo.svg"));
+    assert_eq!(Summary(&issues).to_string(), "fnlint: 3 issues across 2 files\n");
+    assert_eq!(Summary(&[]).to_string(), "fnlint: 0 issues across 0 files\n");
+  }
+
+  #[test]
+  fn test_ndjson_lines() {
+    let rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    let files = ["src/userService.ts", "src/ok-name.ts", "src/Order Item.ts"];
+    let issues = lint_files(files.map(String::from).to_vec(), ".ts".to_string(), &rule);
+    let output = Ndjson(&issues).to_string();
+    let lines =
+      output.lines().map(|line| serde_json::from_str(line).unwrap()).collect::<Vec<Value>>();
+    assert_eq!(lines.len(), issues.len());
+    assert_eq!(lines[0]["path"], "src/userService.ts");
+    assert_eq!(lines[0]["suggestion"], "user-service");
+    assert_eq!(lines[1]["kind"], "case");
+  }
+
+  #[test]
+  fn test_pretty_json() {
+    let rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    let issues = lint_files(vec!["src/userService.ts".to_string()], ".ts".to_string(), &rule);
+    let compact = Json { issues: &issues, pretty: false }.to_string();
+    let pretty = Json { issues: &issues, pretty: true }.to_string();
+    assert_eq!(compact.lines().count(), 1);
+    assert!(pretty.contains("\n  \"issues\": [\n"));
+    assert_eq!(
+      serde_json::from_str::<Value>(&compact).unwrap(),
+      serde_json::from_str::<Value>(&pretty).unwrap()
+    );
+  }
+
+  #[test]
+  fn test_clean_json() {
+    let document = Json { issues: &[], pretty: false }.to_string();
+    let document = serde_json::from_str::<Value>(&document).unwrap();
+    assert_eq!(document["issues"], json!([]));
+    assert_eq!(document["summary"], json!({ "issues": 0, "files": 0 }));
+  }
+
+  #[test]
+  fn test_json_tool_header() {
+    let document = Json { issues: &[], pretty: false }.to_string();
+    let document = serde_json::from_str::<Value>(&document).unwrap();
+    assert_eq!(document["tool"]["name"], "fnlint");
+    assert_eq!(document["tool"]["version"], env!("CARGO_PKG_VERSION"));
+    let document = serde_json::from_str::<Value>(&PrettyJson(&[]).to_string()).unwrap();
+    assert_eq!(document["tool"], tool());
+  }
+
+  #[test]
+  fn test_codeclimate() {
+    let rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    let lint = || {
+      let files = vec!["src/userService.ts".to_string(), "src/Order.ts".to_string()];
+      let issues = lint_files(files, ".ts".to_string(), &rule);
+      issues.into_iter().map(|issue| issue.with_rule(".ts")).collect::<Vec<Issue>>()
+    };
+    let (first, second) = (lint(), lint());
+    assert_eq!(fingerprint(&first[0]), fingerprint(&second[0]));
+    assert_eq!(fingerprint(&first[0]), 0x9fad_e42c_b17d_7392);
+    assert_ne!(fingerprint(&first[0]), fingerprint(&first[1]));
+    let mut rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    rule.full_path = true;
+    let dirs = lint_files(vec!["A/B/x.ts".to_string()], ".ts".to_string(), &rule);
+    assert_eq!(dirs.len(), 2);
+    assert_ne!(fingerprint(&dirs[0]), fingerprint(&dirs[1]));
+    let word = |word: &str| {
+      Issue::new(IssueKind::UnknownWord { word: word.to_string() }, "recieve_adress.md", "a.md")
+    };
+    assert_ne!(fingerprint(&word("recieve")), fingerprint(&word("adress")));
+
+    let report = serde_json::from_str::<Value>(&CodeClimate(&first).to_string()).unwrap();
+    assert_eq!(
+      report[0],
+      json!({
+        "type": "issue",
+        "engine_name": "fnlint",
+        "check_name": "case",
+        "description": "Filename userService in src/userService.ts does not match any of the patterns: kebab-case",
+        "categories": ["Style"],
+        "fingerprint": "9fade42cb17d7392",
+        "severity": "major",
+        "location": { "path": "src/userService.ts", "lines": { "begin": 1 } },
+      })
+    );
+  }
+
+  #[test]
+  fn test_checkstyle() {
+    let mut rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    rule.full_path = true;
+    rule.severity = Severity::Warning;
+    let files = ["src/Api/userService.ts", "src/<tag>.ts", "src/ok.ts"];
+    let issues = lint_files(files.map(String::from).to_vec(), ".ts".to_string(), &rule);
+    let issues = issues.into_iter().map(|issue| issue.with_rule(".ts")).collect::<Vec<_>>();
+    let report = Checkstyle(&issues).to_string();
+    assert_eq!(report.matches("<file ").count(), 2);
+    assert_eq!(report.matches("<error ").count(), 3);
+    assert_eq!(report.matches("</file>").count(), 2);
+    assert!(report.contains(r#"<file name="src/&lt;tag&gt;.ts">"#));
+    assert!(report.contains(r#"severity="warning""#));
+    assert!(report.contains(r#"source="fnlint.ts""#));
+    assert!(!report.contains("fnlint.."));
+    assert!(report.ends_with("</checkstyle>\n"));
+    assert_eq!(
+      Checkstyle(&[]).to_string(),
+      "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<checkstyle version=\"4.3\">\n</checkstyle>\n"
+    );
+  }
+
+  #[test]
+  fn test_text_marks_fixable() {
+    let rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    let mut issues = lint_files(vec!["src/UserService.ts".to_string()], ".ts".to_string(), &rule);
+    let ext = IssueKind::DisallowedExtension { ext: ".orig".to_string() };
+    issues.push(Issue::new(ext, "user-service.ts.orig", "src/user-service.ts.orig"));
+    assert!(issues[0].fixable());
+    assert!(!issues[1].fixable());
+    let lines = Text(&issues).to_string();
+    let lines = lines.lines().collect::<Vec<_>>();
+    assert!(lines[0].ends_with(" kebab-case [fixable]"), "{}", lines[0]);
+    assert_eq!(lines[1], "File src/user-service.ts.orig has the disallowed extension .orig");
+    assert_eq!(issue_json(&issues[0])["fixable"], true);
+    assert_eq!(issue_json(&issues[1])["fixable"], false);
+  }
+
+  #[test]
+  fn test_format_pretty_json() {
+    let rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    let files = ["src/UserService.ts", "src/ok.ts"];
+    let mut issues = lint_files(files.map(String::from).to_vec(), ".ts".to_string(), &rule);
+    issues.push(Issue::new(IssueKind::OrphanTest, "a.test.ts", "src/a.test.ts"));
+    let report = PrettyJson(&issues).to_string();
+    assert!(report.contains("\n  \"issues\": ["));
+    let document = serde_json::from_str::<Value>(&report).unwrap();
+    assert_eq!(
+      document["issues"][0],
+      json!({
+        "path": "src/UserService.ts",
+        "current": "Pascal",
+        "expected": ["kebab-case"],
+        "suggestion": "user-service",
+        "fixable": true,
+      })
+    );
+    assert_eq!(document["issues"][1]["suggestion"], Value::Null);
+    assert_eq!(document["issues"][1]["fixable"], false);
+    assert_eq!(document["summary"], json!({ "issues": 2, "files": 2, "fixable": 1 }));
+  }
+
+  #[test]
+  fn test_teamcity() {
+    let rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    let files = ["src/[id]/userService.ts", "src/OrderItem.ts", "src/ok.ts"];
+    let mut issues = lint_files(files.map(String::from).to_vec(), ".ts".to_string(), &rule);
+    issues[1].severity = Severity::Warning;
+    let report = TeamCity(&issues).to_string();
+    let lines = report.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(
+      lines[0],
+      "##teamcity[inspectionType id='fnlint.case' name='case' category='fnlint' description='fnlint case issues']"
+    );
+    assert!(lines[1].contains("file='src/|[id|]/userService.ts'"));
+    assert!(lines[1].ends_with("SEVERITY='ERROR']"));
+    assert!(lines[2].ends_with("SEVERITY='WARNING']"));
+    assert_eq!(teamcity_escape("it's|a\n[b]"), "it|'s||a|n|[b|]");
+    assert_eq!(TeamCity(&[]).to_string(), "");
+  }
+
+  #[test]
+  fn test_stats() {
+    let rule = FilenameRule::from(vec![FilenameCase::Kebab]);
+    let files =
+      ["src/UserService.ts", "src/OrderItem.ts", "src/my file.ts", "src/a_b.ts", "src/Cart.ts"];
+    let mut issues = lint_files(files.map(String::from).to_vec(), ".ts".to_string(), &rule);
+    issues.push(Issue::new(IssueKind::OrphanTest, "cart.test.ts", "src/cart.test.ts"));
+    assert_eq!(
+      Stats(&issues).to_string(),
+      "3 files are Pascal but should be kebab-case\n\
+       1 file contains spaces\n\
+       1 file is snake_case but should be kebab-case\n\
+       1 test has no source file\n"
+    );
+  }
+
+  #[test]
+  fn test_table_rows() {
+    let rule = FilenameRule::from(vec![FilenameCase::Kebab, FilenameCase::Lower]);
+    let files = ["src/userService.ts", "src/ok-name.ts", "lib/HELLO_WORLD.ts"];
+    let issues = lint_files(files.map(String::from).to_vec(), ".ts".to_string(), &rule);
+    let table = Table(&issues).to_string();
+    assert_eq!(
+      table,
+      "PATH                DETECTED              EXPECTED\n\
+       src/userService.ts  camelCase             kebab-case, lowercase\n\
+       lib/HELLO_WORLD.ts  SCREAMING_SNAKE_CASE  kebab-case, lowercase\n"
+    );
+  }
+}
@@ -0,0 +1,86 @@
+use crate::scan::path::normalize_path;
+use anyhow::{bail, Context, Result};
+use std::fs::File;
+use std::io::{Read, Seek};
+use std::path::Path;
+
+/// The file entries of the archive at `path`, without extracting it. Directories are not listed
+/// on their own; like on disk, they are checked through the files below them.
+pub fn archive_entries(path: &Path) -> Result<Vec<String>> {
+  let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+  let file = File::open(path).with_context(|| format!("Cannot open {}", path.display()))?;
+  if name.ends_with(".zip") {
+    zip_entries(file)
+  } else if name.ends_with(".tar") {
+    tar_entries(file)
+  } else {
+    bail!("Unsupported archive format: {}", path.display())
+  }
+}
+
+pub fn zip_entries<R: Read + Seek>(reader: R) -> Result<Vec<String>> {
+  let mut archive = zip::ZipArchive::new(reader)?;
+  let mut entries = vec![];
+  for index in 0..archive.len() {
+    let entry = archive.by_index_raw(index)?;
+    if entry.is_file() {
+      entries.push(normalize_path(entry.name()));
+    }
+  }
+  Ok(entries)
+}
+
+pub fn tar_entries<R: Read>(reader: R) -> Result<Vec<String>> {
+  let mut archive = tar::Archive::new(reader);
+  let mut entries = vec![];
+  for entry in archive.entries()? {
+    let entry = entry?;
+    if entry.header().entry_type().is_file() {
+      let path = entry.path()?;
+      entries.push(normalize_path(path.to_str().context("Archive entry is not UTF-8")?));
+    }
+  }
+  Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::config::{FilenameCase, FilenameLintConfig};
+  use crate::linter::visitor::lint_filenames;
+  use indexmap::IndexMap;
+  use std::io::{Cursor, Write};
+  use zip::write::SimpleFileOptions;
+
+  #[test]
+  fn test_zip_entries() {
+    let mut writer = zip::ZipWriter::new(Cursor::new(vec![]));
+    writer.add_directory("dist/", SimpleFileOptions::default()).unwrap();
+    for name in ["dist/main-bundle.js", "dist/VendorBundle.js"] {
+      writer.start_file(name, SimpleFileOptions::default()).unwrap();
+      writer.write_all(b"export {};").unwrap();
+    }
+    let archive = writer.finish().unwrap();
+    let entries = zip_entries(archive).unwrap();
+    assert_eq!(entries, vec!["dist/main-bundle.js", "dist/VendorBundle.js"]);
+
+    let config = FilenameLintConfig {
+      ls: IndexMap::from([(".js".to_string(), vec![FilenameCase::Kebab].into())]),
+      ..Default::default()
+    };
+    let issues = lint_filenames(&config, &entries).issues;
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].path, "dist/VendorBundle.js");
+  }
+
+  #[test]
+  fn test_tar_entries() {
+    let mut builder = tar::Builder::new(vec![]);
+    let mut header = tar::Header::new_gnu();
+    header.set_size(0);
+    header.set_cksum();
+    builder.append_data(&mut header.clone(), "docs/Read Me.md", std::io::empty()).unwrap();
+    let archive = builder.into_inner().unwrap();
+    assert_eq!(tar_entries(archive.as_slice()).unwrap(), vec!["docs/Read Me.md"]);
+  }
+}
@@ -0,0 +1,54 @@
+use anyhow::{bail, Context, Result};
+use std::path::{absolute, Path, PathBuf};
+use std::process::Command;
+
+/// The root of the git repository `dir` is in: the nearest of `dir` and its parents holding a
+/// `.git` directory, or a `.git` file as in worktrees and submodules.
+pub fn git_root(dir: &Path) -> Option<PathBuf> {
+  let dir = absolute(dir).ok()?;
+  dir.ancestors().find(|ancestor| ancestor.join(".git").exists()).map(Path::to_path_buf)
+}
+
+/// Files added, copied, modified or renamed in the git index, relative to the working directory.
+/// Deleted files are left out since there is no name left to lint.
+pub fn staged_files() -> Result<Vec<String>> {
+  let output = Command::new("git")
+    .args(["diff", "--cached", "--name-only", "--relative", "--diff-filter=ACMR", "-z"])
+    .output()
+    .context("Cannot run git")?;
+  if !output.status.success() {
+    bail!("git diff --cached failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+  }
+  let files = String::from_utf8(output.stdout).context("git printed a non UTF-8 path")?;
+  Ok(files.split('\0').filter(|file| !file.is_empty()).map(String::from).collect())
+}
+
+/// Every file in the tree of the commit `rev`, e.g. a tag, relative to the working directory as
+/// with [`staged_files`]; only files under the working directory are listed. Nothing is checked
+/// out.
+pub fn rev_files(rev: &str) -> Result<Vec<String>> {
+  let output = Command::new("git")
+    .args(["ls-tree", "-r", "--name-only", "-z", rev, "--"])
+    .output()
+    .context("Cannot run git")?;
+  if !output.status.success() {
+    bail!("git ls-tree {} failed: {}", rev, String::from_utf8_lossy(&output.stderr).trim());
+  }
+  let files = String::from_utf8(output.stdout).context("git printed a non UTF-8 path")?;
+  Ok(files.split('\0').filter(|file| !file.is_empty()).map(String::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn find_git_root() {
+    let dir = tempfile::tempdir().unwrap();
+    let nested = dir.path().join("packages/web");
+    std::fs::create_dir_all(&nested).unwrap();
+    assert_ne!(git_root(&nested).as_deref(), Some(dir.path()));
+    std::fs::write(dir.path().join(".git"), "gitdir: ../main/.git/worktrees/web\n").unwrap();
+    assert_eq!(git_root(&nested).as_deref(), Some(dir.path()));
+  }
+}
@@ -0,0 +1,47 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// The files listed by a manifest of changed files, split by whether they exist.
+#[derive(Debug, Default, PartialEq)]
+pub struct Manifest {
+  pub files: Vec<String>,
+  /// Listed paths that are not a file under the root, e.g. files the change deleted.
+  pub missing: Vec<String>,
+}
+
+/// Read the JSON array of paths at `path`, as CI systems write from their own diff, e.g.
+/// `["src/main.rs", "docs/index.md"]`. Relative paths are taken relative to `root`.
+pub fn read_manifest(path: &Path, root: &Path) -> Result<Manifest> {
+  let content =
+    std::fs::read_to_string(path).with_context(|| format!("Cannot read {}", path.display()))?;
+  let paths = serde_json::from_str::<Vec<String>>(&content)
+    .with_context(|| format!("{} is not a JSON array of paths", path.display()))?;
+  let mut manifest = Manifest::default();
+  for listed in paths.into_iter().filter(|listed| !listed.trim().is_empty()) {
+    match root.join(&listed).is_file() {
+      true => manifest.files.push(listed),
+      false => manifest.missing.push(listed),
+    }
+  }
+  Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn manifest_skips_missing_files() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("src")).unwrap();
+    std::fs::write(dir.path().join("src/main.rs"), "").unwrap();
+    let path = dir.path().join("changed.json");
+    std::fs::write(&path, r#"["src/main.rs", "src/deleted.rs", "src", ""]"#).unwrap();
+    let manifest = read_manifest(&path, dir.path()).unwrap();
+    assert_eq!(manifest.files, vec!["src/main.rs"]);
+    assert_eq!(manifest.missing, vec!["src/deleted.rs", "src"]);
+
+    std::fs::write(&path, r#"{ "files": [] }"#).unwrap();
+    assert!(read_manifest(&path, dir.path()).is_err());
+  }
+}
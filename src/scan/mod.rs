@@ -1,2 +1,7 @@
+#[cfg(feature = "archive")]
+pub mod archive;
+pub mod git;
+pub mod manifest;
+pub mod path;
 pub mod pattern;
 pub mod scanner;
@@ -0,0 +1,125 @@
+use crate::config::PathStyle;
+use std::path::{Component, Path, PathBuf};
+
+/// Resolve `path` against the working directory and drop `.`/`..` components lexically,
+/// without touching the filesystem.
+fn absolute(path: &Path) -> PathBuf {
+  let path = match path.is_absolute() {
+    true => path.to_path_buf(),
+    false => std::env::current_dir().unwrap_or_default().join(path),
+  };
+  let mut result = PathBuf::new();
+  for component in path.components() {
+    match component {
+      Component::CurDir => {}
+      Component::ParentDir => {
+        result.pop();
+      }
+      component => result.push(component),
+    }
+  }
+  result
+}
+
+/// Bring a path into the form used for matching and output: backslashes become `/`, runs of
+/// separators collapse into one and any leading `./` is stripped.
+pub fn normalize_path(path: &str) -> String {
+  let path = path.replace('\\', "/");
+  let mut normalized = String::with_capacity(path.len());
+  for c in path.chars() {
+    if c == '/' && normalized.ends_with('/') {
+      continue;
+    }
+    normalized.push(c);
+  }
+  let mut normalized = normalized.as_str();
+  while let Some(rest) = normalized.strip_prefix("./") {
+    normalized = rest;
+  }
+  normalized.to_string()
+}
+
+/// Express `path` relative to `root`. Paths outside of `root` are returned unchanged.
+pub fn relative_to(path: &str, root: &Path) -> String {
+  match absolute(Path::new(path)).strip_prefix(absolute(root)) {
+    Ok(relative) => relative.to_str().unwrap().to_string(),
+    Err(_) => path.to_string(),
+  }
+}
+
+/// `path`, given relative to the working directory, in `style`; relative paths are expressed
+/// against `relative` as by [`relative_to`].
+pub fn styled_path(path: &str, style: PathStyle, relative: &Path) -> String {
+  match style {
+    PathStyle::Relative => relative_to(path, relative),
+    PathStyle::Absolute => normalize_path(&absolute(Path::new(path)).to_string_lossy()),
+    PathStyle::Uri => file_uri(&normalize_path(&absolute(Path::new(path)).to_string_lossy())),
+  }
+}
+
+/// The `file://` URI of the absolute path `path`, with `/` separators. Bytes other than letters,
+/// digits and `-._~/` are percent-encoded, and a Windows drive such as `C:` keeps its colon.
+fn file_uri(path: &str) -> String {
+  let mut uri = String::from("file://");
+  if !path.starts_with('/') {
+    uri.push('/');
+  }
+  for (index, byte) in path.bytes().enumerate() {
+    let drive = index == 1 && byte == b':';
+    match byte.is_ascii_alphanumeric() || b"-._~/".contains(&byte) || drive {
+      true => uri.push(byte as char),
+      false => uri.push_str(&format!("%{:02X}", byte)),
+    }
+  }
+  uri
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn normalize_strips_leading_dot() {
+    assert_eq!(normalize_path("./src/main.rs"), "src/main.rs");
+    assert_eq!(normalize_path("././src/main.rs"), "src/main.rs");
+    assert_eq!(normalize_path("src/./main.rs"), "src/./main.rs");
+  }
+
+  #[test]
+  fn normalize_converts_backslashes() {
+    assert_eq!(normalize_path("src\\linter\\mod.rs"), "src/linter/mod.rs");
+    assert_eq!(normalize_path(".\\src\\main.rs"), "src/main.rs");
+  }
+
+  #[test]
+  fn normalize_collapses_separators() {
+    assert_eq!(normalize_path("src//linter///mod.rs"), "src/linter/mod.rs");
+    assert_eq!(normalize_path("/tmp//repo/a.rs"), "/tmp/repo/a.rs");
+    assert_eq!(normalize_path(".//src/main.rs"), "src/main.rs");
+  }
+
+  #[test]
+  fn test_relative_to() {
+    let root = Path::new("/home/runner/work/repo");
+    assert_eq!(relative_to("/home/runner/work/repo/src/foo.ts", root), "src/foo.ts");
+    assert_eq!(relative_to("/home/runner/work/repo/./src/../lib/a.ts", root), "lib/a.ts");
+    assert_eq!(relative_to("/tmp/elsewhere.ts", root), "/tmp/elsewhere.ts");
+    assert_eq!(relative_to("./src/main.rs", Path::new(".")), "src/main.rs");
+  }
+
+  #[test]
+  fn path_styles() {
+    let path = "/home/runner/work/repo/src/Order Item.ts";
+    let root = Path::new("/home/runner/work/repo");
+    assert_eq!(styled_path(path, PathStyle::Relative, root), "src/Order Item.ts");
+    assert_eq!(styled_path(path, PathStyle::Absolute, root), path);
+    assert_eq!(
+      styled_path(path, PathStyle::Uri, root),
+      "file:///home/runner/work/repo/src/Order%20Item.ts"
+    );
+    let cwd = normalize_path(&std::env::current_dir().unwrap().to_string_lossy());
+    let absolute = styled_path("./src/main.rs", PathStyle::Absolute, Path::new("."));
+    assert_eq!(absolute, format!("{}/src/main.rs", cwd));
+    assert_eq!(file_uri("C:/repo/a#b.ts"), "file:///C:/repo/a%23b.ts");
+  }
+}
@@ -1 +1,110 @@
+use anyhow::{bail, Result};
+use regex::Regex;
 
+/// A glob pattern matched against paths relative to the scan root.
+///
+/// `*` matches within a single path segment, `**` matches across segments and `?` matches a
+/// single character. A brace set matches any of its comma-separated alternatives, so
+/// `src/*.{ts,tsx}` covers both extensions; sets may nest. A pattern also matches everything
+/// below a path it matches, so `src` covers `src/main.rs`.
+pub struct Pattern {
+  regex: Regex,
+}
+
+impl Pattern {
+  /// Errors on an unbalanced brace, as in `src/{a,b`.
+  pub fn new(glob: &str) -> Result<Self> {
+    Ok(Pattern { regex: Regex::new(&format!("^{}(/.*)?$", glob_to_regex(glob)?))? })
+  }
+
+  /// A pattern matching only the paths the glob names, not what is below them.
+  pub fn exact(glob: &str) -> Result<Self> {
+    Ok(Pattern { regex: Regex::new(&format!("^{}$", glob_to_regex(glob)?))? })
+  }
+
+  pub fn matches(&self, path: &str) -> bool {
+    self.regex.is_match(path)
+  }
+}
+
+impl TryFrom<&str> for Pattern {
+  type Error = anyhow::Error;
+
+  fn try_from(glob: &str) -> Result<Self> {
+    Pattern::new(glob)
+  }
+}
+
+fn glob_to_regex(glob: &str) -> Result<String> {
+  let mut regex = String::new();
+  let mut depth = 0;
+  let mut chars = glob.trim_end_matches('/').chars().peekable();
+  while let Some(c) = chars.next() {
+    match c {
+      '*' if chars.peek() == Some(&'*') => {
+        chars.next();
+        if chars.peek() == Some(&'/') {
+          chars.next();
+          regex.push_str("(.*/)?");
+        } else {
+          regex.push_str(".*");
+        }
+      }
+      '*' => regex.push_str("[^/]*"),
+      '?' => regex.push_str("[^/]"),
+      '{' => {
+        depth += 1;
+        regex.push_str("(?:");
+      }
+      '}' if depth > 0 => {
+        depth -= 1;
+        regex.push(')');
+      }
+      '}' => bail!("Unmatched `}}` in glob {}", glob),
+      ',' if depth > 0 => regex.push('|'),
+      _ => regex.push_str(&regex::escape(&c.to_string())),
+    }
+  }
+  if depth > 0 {
+    bail!("Unclosed `{{` in glob {}", glob);
+  }
+  Ok(regex)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_glob_patterns() {
+    let pattern = Pattern::new("src/**").unwrap();
+    assert!(pattern.matches("src/main.rs"));
+    assert!(pattern.matches("src/config/mod.rs"));
+    assert!(!pattern.matches("docs/index.md"));
+    let pattern = Pattern::new("src/**/*.rs").unwrap();
+    assert!(pattern.matches("src/main.rs"));
+    assert!(pattern.matches("src/config/mod.rs"));
+    assert!(!pattern.matches("src/config/mod.ts"));
+    assert!(Pattern::new("docs").unwrap().matches("docs/index.md"));
+  }
+
+  #[test]
+  fn test_brace_sets() {
+    let pattern = Pattern::new("src/*.{ts,tsx}").unwrap();
+    assert!(pattern.matches("src/app.ts"));
+    assert!(pattern.matches("src/app.tsx"));
+    assert!(!pattern.matches("src/app.js"));
+    let pattern = Pattern::try_from("{lib,bin/{cli,server}}/*.rs").unwrap();
+    assert!(pattern.matches("lib/mod.rs"));
+    assert!(pattern.matches("bin/server/main.rs"));
+    assert!(!pattern.matches("bin/main.rs"));
+    assert!(Pattern::new("a,b").unwrap().matches("a,b"));
+  }
+
+  #[test]
+  fn test_invalid_globs() {
+    assert!(Pattern::new("src/{a,b").is_err());
+    assert!(Pattern::new("src/a}").is_err());
+    assert!(Pattern::exact("{src").is_err());
+  }
+}
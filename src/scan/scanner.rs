@@ -1,80 +1,463 @@
+use crate::scan::path::{normalize_path, relative_to};
+use crate::scan::pattern::Pattern;
+use anyhow::{bail, Context, Result};
+use std::path::Path;
+use std::time::{Duration, Instant};
 use walkdir::{DirEntry, WalkDir};
 
-fn is_ignored(entry: &DirEntry, ignore: &Vec<String>) -> bool {
-  let path = entry.path();
-  let path_str = path.to_str().unwrap();
-  // 1. the folder: e.g. `node_modules` in ignore, so the folder of `node_modules` will be ignored
-  // 2. the file: e.g. `*.log` in ignore, so the file of `server.log` will be ignored
-  // 3. glob folder pattern: e.g. `**/config` in ignore, so the folder of `src/config` will be ignored
-  ignore.iter().any(|pattern| {
-    let levels = pattern.split('/').collect::<Vec<&str>>();
-    // 1. any level contains the ignore pattern
-    if levels.iter().any(|level| path_str.contains(level)) {
-      return true;
-    }
-    // 2. check the file name
-    if path.is_file() && path_str.contains(pattern) {
-      return true;
-    }
-    // 3. handle glob folder pattern
-    if pattern.contains("**") {
-      let mut pattern = pattern.replace("**", "");
-      if pattern.ends_with('/') {
-        pattern.pop();
-      }
-      let pattern = format!("{}$", pattern);
-      let re = regex::Regex::new(&pattern).unwrap();
-      return re.is_match(path_str);
+/// A compiled `ignore` entry.
+pub(crate) struct IgnorePattern {
+  pattern: Pattern,
+  /// Patterns containing a `/` are matched against the whole path relative to the scan root.
+  anchored: bool,
+  /// Patterns ending in `/` only match directories.
+  dir_only: bool,
+}
+
+impl IgnorePattern {
+  pub(crate) fn new(pattern: &str) -> Result<Self> {
+    let dir_only = pattern.ends_with('/');
+    let anchored = pattern.trim_end_matches('/').contains('/');
+    let pattern = Pattern::new(pattern.trim_end_matches('/'))
+      .with_context(|| format!("Invalid ignore pattern {:?}", pattern))?;
+    Ok(IgnorePattern { pattern, anchored, dir_only })
+  }
+
+  fn matches(&self, path: &Path, is_dir: bool) -> bool {
+    if self.dir_only && !is_dir {
+      return false;
     }
-    // 4. handle the glob file pattern
-    if pattern.contains('*') {
-      let filename = path.file_name().unwrap().to_str().unwrap();
-      let re = regex::Regex::new(&pattern.replace("*", ".*")).unwrap();
-      return re.is_match(filename);
+    if self.anchored {
+      return self.pattern.matches(&path.to_string_lossy());
     }
-    false
+    // otherwise the pattern names a single component anywhere in the tree: e.g. `node_modules`
+    // ignores every folder of that name, `*.log` ignores the file of `server.log`.
+    // Components are compared whole, so `test` does not ignore `latest.ts`.
+    path.file_name().is_some_and(|name| self.pattern.matches(&name.to_string_lossy()))
+  }
+}
+
+/// Settings for a walk besides what to ignore and include.
+#[derive(Debug, Default, Clone)]
+pub struct ScanOptions {
+  /// Also scan files and directories whose name starts with a dot, such as `.git/`.
+  pub hidden: bool,
+  /// Only keep files whose path relative to the scan root contains this substring.
+  pub path_filter: Option<String>,
+  /// Stop walking after this long, e.g. when pointed at a huge network mount by mistake.
+  pub timeout: Option<Duration>,
+  /// Warn about paths longer than this many bytes, which some tools and systems cannot open.
+  /// Files under such paths are still linted.
+  pub max_path_length: Option<usize>,
+  /// Walk into symlinked directories. A link is always listed under its own path, so it is
+  /// its name, not the target's, that is linted.
+  pub follow_symlinks: bool,
+  /// With `follow_symlinks`, warn about links whose target lies outside the scan root.
+  pub warn_external_symlinks: bool,
+}
+
+/// The files a walk found, and whether it stopped early at the [timeout](ScanOptions::timeout).
+#[derive(Debug, Default)]
+pub struct Scan {
+  pub files: Vec<String>,
+  pub timed_out: bool,
+  /// Entries that could not be walked, and paths over the
+  /// [`max_path_length`](ScanOptions::max_path_length).
+  pub warnings: Vec<String>,
+}
+
+/// Yield from `entries` until `deadline` passes, then record the timeout and stop.
+fn until_deadline<'a, T>(
+  entries: impl Iterator<Item = T> + 'a,
+  deadline: Option<Instant>,
+  timed_out: &'a mut bool,
+) -> impl Iterator<Item = T> + 'a {
+  entries.take_while(move |_| {
+    *timed_out = deadline.is_some_and(|deadline| Instant::now() >= deadline);
+    !*timed_out
   })
 }
 
-pub fn scan_dir(base: &str, ignore: &Vec<String>) -> Vec<String> {
-  let walker = WalkDir::new(base).into_iter();
-  walker
-    .filter_map(Result::ok)
-    .filter(|entry| !is_ignored(entry, ignore))
+impl ScanOptions {
+  fn keeps(&self, path: &str) -> bool {
+    self.path_filter.as_deref().map_or(true, |filter| path.contains(filter))
+  }
+}
+
+fn is_hidden(name: &str) -> bool {
+  name.starts_with('.') && name != "." && name != ".."
+}
+
+/// Ignored directories are pruned from the walk, so their contents are never visited.
+fn is_ignored(
+  entry: &DirEntry,
+  base: &str,
+  ignore: &[IgnorePattern],
+  options: &ScanOptions,
+) -> bool {
+  // the root is always walked, even when it is `.` or a hidden directory itself
+  if entry.depth() == 0 {
+    return false;
+  }
+  if !options.hidden && entry.file_name().to_str().is_some_and(is_hidden) {
+    return true;
+  }
+  let path = entry.path().strip_prefix(base).unwrap_or(entry.path());
+  let is_dir = entry.file_type().is_dir();
+  ignore.iter().any(|pattern| pattern.matches(path, is_dir))
+}
+
+/// A file is included when `include` is empty or any include pattern matches its path relative
+/// to `base`. Ignore rules are checked first, so an ignored file is never included.
+fn is_included(entry: &DirEntry, base: &str, include: &[Pattern]) -> bool {
+  if include.is_empty() {
+    return true;
+  }
+  let path = entry.path().strip_prefix(base).unwrap_or(entry.path());
+  let path_str = path.to_string_lossy();
+  include.iter().any(|pattern| pattern.matches(&path_str))
+}
+
+fn compile(ignore: &[String], include: &[String]) -> Result<(Vec<IgnorePattern>, Vec<Pattern>)> {
+  let ignore = ignore.iter().map(|pattern| IgnorePattern::new(pattern)).collect::<Result<_>>()?;
+  let include = include
+    .iter()
+    .map(|glob| Pattern::new(glob).with_context(|| format!("Invalid include pattern {:?}", glob)))
+    .collect::<Result<_>>()?;
+  Ok((ignore, include))
+}
+
+/// Walk `base` and collect every file that is not ignored, ignoring whether the walk timed out;
+/// see [`scan`]. Names that are not valid UTF-8 are
+/// matched and returned with the invalid bytes replaced by `U+FFFD`, rather than dropped.
+///
+/// Errors when `base` is missing, unreadable or not a directory, instead of reporting that an
+/// empty tree has no issues.
+pub fn scan_dir(
+  base: &str,
+  ignore: &[String],
+  include: &[String],
+  options: &ScanOptions,
+) -> Result<Vec<String>> {
+  Ok(scan(base, ignore, include, options)?.files)
+}
+
+/// Like [`scan_dir`], also telling whether the walk was cut short by the timeout, in which case
+/// only the files found until then are returned. The deadline is checked before each entry.
+pub fn scan(
+  base: &str,
+  ignore: &[String],
+  include: &[String],
+  options: &ScanOptions,
+) -> Result<Scan> {
+  let mut files = vec![];
+  let scan = scan_each(base, ignore, include, options, |file| files.push(file))?;
+  Ok(Scan { files, ..scan })
+}
+
+/// Like [`scan`], handing each file to `each` as soon as it is found rather than collecting
+/// them, so the returned [`Scan`] lists no files.
+pub fn scan_each(
+  base: &str,
+  ignore: &[String],
+  include: &[String],
+  options: &ScanOptions,
+  each: impl FnMut(String),
+) -> Result<Scan> {
+  let deadline = options.timeout.map(|timeout| Instant::now() + timeout);
+  let metadata = std::fs::metadata(base).with_context(|| format!("Cannot scan {}", base))?;
+  if !metadata.is_dir() {
+    bail!("Cannot scan {}: not a directory", base);
+  }
+  let (ignore, include) = compile(ignore, include)?;
+  let root = match options.follow_symlinks && options.warn_external_symlinks {
+    true => std::fs::canonicalize(base).ok(),
+    false => None,
+  };
+  let walker = WalkDir::new(base).follow_links(options.follow_symlinks).into_iter();
+  let mut timed_out = false;
+  let mut warnings = vec![];
+  let entries = walker.filter_entry(|entry| !is_ignored(entry, base, &ignore, options));
+  until_deadline(entries, deadline, &mut timed_out)
+    .filter_map(|entry| match entry {
+      Ok(entry) => {
+        warnings.extend(too_long(entry.path(), options.max_path_length));
+        warnings.extend(external_link(&entry, root.as_deref()));
+        Some(entry)
+      }
+      Err(error) => {
+        let path = error.path().map(|path| path.display().to_string()).unwrap_or_default();
+        warnings.push(format!("Cannot walk {}: {}", path, error));
+        None
+      }
+    })
     .filter(|entry| entry.path().is_file())
-    .map(|entry| entry.path().to_str().unwrap().to_string())
-    .collect()
+    .filter(|entry| is_included(entry, base, &include))
+    .filter(|entry| {
+      let path = entry.path().strip_prefix(base).unwrap_or(entry.path());
+      options.keeps(&path.to_string_lossy())
+    })
+    .map(|entry| normalize_path(&entry.path().to_string_lossy()))
+    .for_each(each);
+  Ok(Scan { files: vec![], timed_out, warnings })
+}
+
+/// A warning when `path` is the first along its ancestors to exceed `max` bytes, so a deep
+/// subtree past the limit is reported once rather than for every entry in it.
+fn too_long(path: &Path, max: Option<usize>) -> Option<String> {
+  let max = max?;
+  let length = path.as_os_str().len();
+  let parent = path.parent().map_or(0, |parent| parent.as_os_str().len());
+  (length > max && parent <= max)
+    .then(|| format!("Path of {} bytes exceeds {}: {}", length, max, path.display()))
+}
+
+/// A warning when `entry` is a symlink whose target lies outside `root`, the canonical scan root.
+fn external_link(entry: &DirEntry, root: Option<&Path>) -> Option<String> {
+  let root = root?;
+  if !entry.path_is_symlink() {
+    return None;
+  }
+  let target = std::fs::canonicalize(entry.path()).ok()?;
+  (!target.starts_with(root)).then(|| {
+    let path = normalize_path(&entry.path().to_string_lossy());
+    format!("Symlink {} points outside the scan root: {}", path, target.display())
+  })
+}
+
+/// Apply the same ignore and include rules as [`scan_dir`] to a list of paths, e.g. the files
+/// staged in git. A path is ignored when any of its directories is. Absolute paths are first
+/// made relative to `root`, since that is what the patterns are written against.
+pub fn filter_paths(
+  root: &Path,
+  paths: Vec<String>,
+  ignore: &[String],
+  include: &[String],
+  options: &ScanOptions,
+) -> Result<Vec<String>> {
+  let (ignore, include) = compile(ignore, include)?;
+  Ok(
+    paths
+      .into_iter()
+      .map(|path| match Path::new(&path).is_absolute() {
+        true => normalize_path(&relative_to(&path, root)),
+        false => normalize_path(&path),
+      })
+      .filter(|path| !is_ignored_path(path, &ignore, options.hidden))
+      .filter(|path| include.is_empty() || include.iter().any(|pattern| pattern.matches(path)))
+      .filter(|path| options.keeps(path))
+      .collect(),
+  )
+}
+
+/// Whether the file at `path`, or any of its directories, is ignored, counting hidden names as
+/// ignored unless `hidden` is set.
+pub(crate) fn is_ignored_path(path: &str, ignore: &[IgnorePattern], hidden: bool) -> bool {
+  let path = Path::new(path);
+  path.ancestors().filter(|ancestor| !ancestor.as_os_str().is_empty()).any(|ancestor| {
+    let is_dir = ancestor != path;
+    let name = ancestor.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+    (!hidden && is_hidden(name)) || ignore.iter().any(|pattern| pattern.matches(ancestor, is_dir))
+  })
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
+  use std::fs;
+  use tempfile::TempDir;
+
+  fn fixture(files: &[&str]) -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    for file in files {
+      let path = dir.path().join(file);
+      fs::create_dir_all(path.parent().unwrap()).unwrap();
+      fs::write(path, "").unwrap();
+    }
+    dir
+  }
+
+  fn scan_relative(dir: &TempDir, ignore: &[String], include: &[String]) -> Vec<String> {
+    let base = dir.path().to_str().unwrap();
+    let mut files = scan_dir(base, ignore, include, &ScanOptions::default())
+      .unwrap()
+      .into_iter()
+      .map(|file| file.trim_start_matches(base).trim_start_matches('/').to_string())
+      .collect::<Vec<String>>();
+    files.sort();
+    files
+  }
 
   #[test]
   fn test_scan_dir() {
-    let files = scan_dir("src", &vec!["main.rs".to_string()]);
+    let files = scan_dir("src", &["main.rs".to_string()], &[], &ScanOptions::default()).unwrap();
     assert!(files.contains(&"src/config/mod.rs".to_string()));
     assert!(!files.contains(&"src/main.rs".to_string()));
   }
 
   #[test]
   fn test_glob_no_config_folder() {
-    let files = scan_dir("src", &vec!["config/**".to_string()]);
+    let files = scan_dir("src", &["config/**".to_string()], &[], &ScanOptions::default()).unwrap();
     assert!(!files.contains(&"src/config/mod.rs".to_string()));
     assert!(files.contains(&"src/main.rs".to_string()));
   }
 
   #[test]
   fn test_no_config_folder() {
-    let files = scan_dir("src", &vec!["config".to_string()]);
+    let files = scan_dir("src", &["config".to_string()], &[], &ScanOptions::default()).unwrap();
     assert!(!files.contains(&"src/config/mod.rs".to_string()));
     assert!(files.contains(&"src/main.rs".to_string()));
   }
 
   #[test]
   fn test_glob_no_rs() {
-    let files = scan_dir("src", &vec!["*.rs".to_string()]);
+    let files = scan_dir("src", &["*.rs".to_string()], &[], &ScanOptions::default()).unwrap();
     assert!(!files.contains(&"src/config/mod.rs".to_string()));
     assert!(!files.contains(&"src/main.rs".to_string()));
   }
+
+  #[test]
+  fn test_include_src_only() {
+    let dir = fixture(&["src/main.rs", "src/config/mod.rs", "docs/index.md"]);
+    let files = scan_relative(&dir, &[], &["src/**".to_string()]);
+    assert_eq!(files, vec!["src/config/mod.rs", "src/main.rs"]);
+  }
+
+  #[test]
+  fn test_ignore_wins_over_include() {
+    let dir = fixture(&["src/main.rs", "src/config/mod.rs", "docs/index.md"]);
+    let files = scan_relative(&dir, &["config".to_string()], &["src/**".to_string()]);
+    assert_eq!(files, vec!["src/main.rs"]);
+  }
+
+  #[test]
+  fn test_ignore_matches_whole_components() {
+    let dir = fixture(&["src/test/a.ts", "src/latest.ts", "src/greatest/b.ts"]);
+    let files = scan_relative(&dir, &["test".to_string()], &[]);
+    assert_eq!(files, vec!["src/greatest/b.ts", "src/latest.ts"]);
+    let dir = fixture(&["src/test", "lib/test/a.ts"]);
+    let files = scan_relative(&dir, &["test/".to_string()], &[]);
+    assert_eq!(files, vec!["src/test"]);
+  }
+
+  #[test]
+  fn test_filter_paths() {
+    let paths = ["src/main.rs", "src/config/mod.rs", "docs/index.md", "target/debug/build.rs"];
+    let files = filter_paths(
+      Path::new("."),
+      paths.map(String::from).to_vec(),
+      &["config".to_string(), "target/".to_string()],
+      &["src/**".to_string(), "target/**".to_string()],
+      &ScanOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(files, vec!["src/main.rs"]);
+  }
+
+  #[test]
+  fn test_skip_hidden() {
+    let dir = fixture(&[".git/config", ".eslintrc.js", "src/main.ts"]);
+    assert_eq!(scan_relative(&dir, &[], &[]), vec!["src/main.ts"]);
+    let base = dir.path().to_str().unwrap();
+    let options = ScanOptions { hidden: true, ..ScanOptions::default() };
+    let files = scan_dir(base, &[], &[], &options).unwrap();
+    assert!(files.iter().any(|file| file.ends_with("/.git/config")));
+    assert!(files.iter().any(|file| file.ends_with("/.eslintrc.js")));
+
+    let staged = vec![".git/config".to_string(), "src/main.ts".to_string()];
+    assert_eq!(
+      filter_paths(Path::new("."), staged.clone(), &[], &[], &ScanOptions::default()).unwrap(),
+      vec!["src/main.ts"]
+    );
+    assert_eq!(filter_paths(Path::new("."), staged.clone(), &[], &[], &options).unwrap(), staged);
+  }
+
+  #[test]
+  fn test_path_filter() {
+    let dir = fixture(&["legacy/OldName.ts", "src/legacy/UserService.ts", "src/NewName.ts"]);
+    let base = dir.path().to_str().unwrap();
+    let options = ScanOptions { path_filter: Some("legacy".to_string()), ..ScanOptions::default() };
+    let mut files = scan_dir(base, &["src".to_string()], &[], &options).unwrap();
+    files.iter_mut().for_each(|file| *file = relative_to(file, dir.path()));
+    assert_eq!(files, vec!["legacy/OldName.ts"]);
+
+    let paths = ["legacy/OldName.ts", "src/legacy/UserService.ts", "src/NewName.ts"];
+    let files =
+      filter_paths(Path::new("."), paths.map(String::from).to_vec(), &[], &[], &options).unwrap();
+    assert_eq!(files, vec!["legacy/OldName.ts", "src/legacy/UserService.ts"]);
+  }
+
+  #[test]
+  fn test_filter_absolute_paths() {
+    let root = Path::new("/home/runner/work/repo");
+    let paths = vec![
+      "/home/runner/work/repo/target/debug/build.rs".to_string(),
+      "/home/runner/work/repo/src/main.rs".to_string(),
+    ];
+    let files =
+      filter_paths(root, paths, &["target".to_string()], &[], &ScanOptions::default()).unwrap();
+    assert_eq!(files, vec!["src/main.rs"]);
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn test_non_utf8_name() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+    let dir = fixture(&["src/main.ts"]);
+    let name = OsStr::from_bytes(b"Bad\xffName.ts");
+    // some filesystems, such as macOS ones, reject names that are not UTF-8
+    if fs::write(dir.path().join("src").join(name), "").is_err() {
+      return;
+    }
+    let files = scan_relative(&dir, &["*.log".to_string()], &["src/**".to_string()]);
+    assert_eq!(files, vec!["src/Bad\u{FFFD}Name.ts", "src/main.ts"]);
+  }
+
+  #[test]
+  fn test_timeout() {
+    let slow = (0..100).map(|n| {
+      std::thread::sleep(Duration::from_millis(2));
+      n
+    });
+    let mut timed_out = false;
+    let deadline = Some(Instant::now() + Duration::from_millis(20));
+    let walked = until_deadline(slow, deadline, &mut timed_out).count();
+    assert!(walked < 100);
+    assert!(timed_out);
+
+    let mut timed_out = false;
+    assert_eq!(until_deadline(0..100, None, &mut timed_out).count(), 100);
+    assert!(!timed_out);
+
+    let dir = fixture(&["src/main.ts"]);
+    let options = ScanOptions { timeout: Some(Duration::ZERO), ..ScanOptions::default() };
+    let scan = scan(dir.path().to_str().unwrap(), &[], &[], &options).unwrap();
+    assert!(scan.timed_out);
+    assert!(scan.files.is_empty());
+  }
+
+  #[test]
+  fn test_long_path_warning() {
+    let deep = (0..12).map(|n| format!("level{:02}", n)).collect::<Vec<_>>().join("/");
+    let dir = fixture(&[&format!("{}/file.ts", deep), "short.ts"]);
+    let base = dir.path().to_str().unwrap();
+    let max = base.len() + 50;
+    let options = ScanOptions { max_path_length: Some(max), ..ScanOptions::default() };
+    let scan = scan(base, &[], &[], &options).unwrap();
+    assert_eq!(scan.files.len(), 2);
+    assert_eq!(scan.warnings.len(), 1);
+    assert!(scan.warnings[0].starts_with(&format!("Path of {} bytes exceeds {}", max + 6, max)));
+
+    let scan = super::scan(base, &[], &[], &ScanOptions::default()).unwrap();
+    assert!(scan.warnings.is_empty());
+  }
+
+  #[test]
+  fn test_missing_base_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let missing = dir.path().join("missing");
+    let error = scan_dir(missing.to_str().unwrap(), &[], &[], &ScanOptions::default()).unwrap_err();
+    assert!(error.to_string().starts_with("Cannot scan"));
+  }
 }
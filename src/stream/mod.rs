@@ -0,0 +1,263 @@
+use crate::config::FilenameLintConfig;
+use crate::linter::rule::{LintContext, Registry};
+use crate::linter::visitor::{find_root, is_generated, lint_each, lint_filenames, LintError};
+use crate::linter::Issue;
+use crate::scan::path::normalize_path;
+use crate::scan::scanner::{scan_each, Scan, ScanOptions};
+use anyhow::Result;
+use futures_core::Stream;
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// What the walking thread hands over to the [`ScanStream`].
+#[derive(Default)]
+struct Channel {
+  files: VecDeque<String>,
+  /// Set once the walk is over, with its warnings or why it failed.
+  outcome: Option<Result<Scan>>,
+  waker: Option<Waker>,
+}
+
+/// The files of a walk, yielded as they are found. The walk runs on a thread of its own, so
+/// polling never blocks.
+pub struct ScanStream {
+  channel: Arc<Mutex<Channel>>,
+  outcome: Option<Result<Scan>>,
+}
+
+/// Walk `base` like [`scan`](crate::scan::scanner::scan), with the same ignore and include
+/// patterns, as a stream of the files found.
+pub fn scan_stream(
+  base: &str,
+  ignore: &[String],
+  include: &[String],
+  options: &ScanOptions,
+) -> ScanStream {
+  let channel = Arc::new(Mutex::new(Channel::default()));
+  let (base, ignore, include, options) =
+    (base.to_string(), ignore.to_vec(), include.to_vec(), options.clone());
+  let sender = channel.clone();
+  std::thread::spawn(move || {
+    let wake = |channel: &mut Channel| channel.waker.take().into_iter().for_each(Waker::wake);
+    let outcome = scan_each(&base, &ignore, &include, &options, |file| {
+      let mut channel = sender.lock().unwrap();
+      channel.files.push_back(file);
+      wake(&mut channel);
+    });
+    let mut channel = sender.lock().unwrap();
+    channel.outcome = Some(outcome);
+    wake(&mut channel);
+  });
+  ScanStream { channel, outcome: None }
+}
+
+impl ScanStream {
+  /// Once the stream has ended, whether the walk timed out and its warnings, or the error that
+  /// stopped it, such as a missing `base`. `None` before the end, and after the first call.
+  pub fn outcome(&mut self) -> Option<Result<Scan>> {
+    self.outcome.take()
+  }
+}
+
+impl Stream for ScanStream {
+  type Item = String;
+
+  fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<String>> {
+    let mut channel = self.channel.lock().unwrap();
+    if let Some(file) = channel.files.pop_front() {
+      return Poll::Ready(Some(file));
+    }
+    match channel.outcome.take() {
+      Some(outcome) => {
+        drop(channel);
+        self.outcome = Some(outcome);
+        Poll::Ready(None)
+      }
+      None => {
+        channel.waker = Some(cx.waker().clone());
+        Poll::Pending
+      }
+    }
+  }
+}
+
+/// The issues of the files of `paths`, as a stream. The checks of a single file are run as
+/// soon as it arrives, so a name in the wrong case is reported right away; the checks that
+/// need every file, such as `testFiles` and `requiredFiles`, and the files under `roots`
+/// follow once `paths` ends. In all, the issues are those [`lint_filenames`] reports.
+pub struct LintStream<'a, S> {
+  config: &'a FilenameLintConfig,
+  paths: S,
+  registry: Registry,
+  ctx: LintContext<'a>,
+  files: Vec<String>,
+  pending: VecDeque<Issue>,
+  /// The issues already yielded, and how often, so the final report does not repeat them.
+  yielded: HashMap<Issue, usize>,
+  errors: Vec<LintError>,
+  done: bool,
+}
+
+pub fn lint_stream<S>(config: &FilenameLintConfig, paths: S) -> LintStream<'_, S>
+where
+  S: Stream<Item = String> + Unpin,
+{
+  LintStream {
+    config,
+    paths,
+    registry: Registry::default(),
+    ctx: LintContext::new(config, &[]),
+    files: vec![],
+    pending: VecDeque::new(),
+    yielded: HashMap::new(),
+    errors: vec![],
+    done: false,
+  }
+}
+
+impl<S> LintStream<'_, S> {
+  /// The files that could not be checked, known once the stream has ended.
+  pub fn errors(&self) -> &[LintError] {
+    &self.errors
+  }
+
+  /// The issues of `file` that can be told without the other files. Files under a root, and
+  /// generated files, are left to the final report.
+  fn lint_arrived(&mut self, file: &str) {
+    if find_root(&self.config.roots, file).is_some() {
+      return;
+    }
+    if let Some(marker) = &self.config.generated_marker {
+      if is_generated(Path::new(file), marker).unwrap_or(true) {
+        return;
+      }
+    }
+    for issue in lint_each(&self.registry, &self.ctx, file) {
+      *self.yielded.entry(issue.clone()).or_default() += 1;
+      self.pending.push_back(issue);
+    }
+  }
+
+  fn finish(&mut self) {
+    let report = lint_filenames(self.config, &self.files);
+    for issue in report.issues {
+      match self.yielded.get_mut(&issue) {
+        Some(count) if *count > 0 => *count -= 1,
+        _ => self.pending.push_back(issue),
+      }
+    }
+    self.errors = report.errors;
+    self.done = true;
+  }
+}
+
+impl<S> Stream for LintStream<'_, S>
+where
+  S: Stream<Item = String> + Unpin,
+{
+  type Item = Issue;
+
+  fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Issue>> {
+    let this = &mut *self;
+    loop {
+      if let Some(issue) = this.pending.pop_front() {
+        return Poll::Ready(Some(issue));
+      }
+      if this.done {
+        return Poll::Ready(None);
+      }
+      match Pin::new(&mut this.paths).poll_next(cx) {
+        Poll::Ready(Some(path)) => {
+          let path = normalize_path(&path);
+          this.lint_arrived(&path);
+          this.files.push(path);
+        }
+        Poll::Ready(None) => this.finish(),
+        Poll::Pending => return Poll::Pending,
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::config::{FilenameCase, TestFileConfig};
+  use crate::linter::IssueKind;
+  use crate::scan::scanner::scan;
+  use indexmap::IndexMap;
+  use std::collections::HashMap as Map;
+  use std::fs;
+  use std::task::Wake;
+  use std::thread::Thread;
+
+  struct Unpark(Thread);
+
+  impl Wake for Unpark {
+    fn wake(self: Arc<Self>) {
+      self.0.unpark();
+    }
+  }
+
+  /// Poll `stream` to its end on the current thread, parking it while nothing is ready.
+  fn collect<S: Stream + Unpin>(stream: &mut S) -> Vec<S::Item> {
+    let waker = Waker::from(Arc::new(Unpark(std::thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut items = vec![];
+    loop {
+      match Pin::new(&mut *stream).poll_next(&mut cx) {
+        Poll::Ready(Some(item)) => items.push(item),
+        Poll::Ready(None) => return items,
+        Poll::Pending => std::thread::park(),
+      }
+    }
+  }
+
+  #[test]
+  fn stream_matches_synchronous_lint() {
+    let dir = tempfile::tempdir().unwrap();
+    let files = ["src/UserService.ts", "src/order-item.ts", "src/cart.test.ts", "a/b_c.ts"];
+    for file in files {
+      let path = dir.path().join(file);
+      fs::create_dir_all(path.parent().unwrap()).unwrap();
+      fs::write(path, "").unwrap();
+    }
+    let config = FilenameLintConfig {
+      ls: IndexMap::from([(".ts".to_string(), vec![FilenameCase::Kebab].into())]),
+      test_files: Some(TestFileConfig { suffixes: vec![".test".to_string()] }),
+      no_shadowed_dirs: true,
+      ..Default::default()
+    };
+    let base = dir.path().to_str().unwrap();
+    let options = ScanOptions::default();
+
+    let mut paths = scan_stream(base, &[], &[], &options);
+    let mut scanned = collect(&mut paths);
+    assert!(!paths.outcome().unwrap().unwrap().timed_out);
+    let mut expected = scan(base, &[], &[], &options).unwrap().files;
+    scanned.sort();
+    expected.sort();
+    assert_eq!(scanned, expected);
+
+    let tally = |issues: Vec<Issue>| {
+      let mut tally = Map::new();
+      issues.into_iter().for_each(|issue| *tally.entry(issue).or_insert(0) += 1);
+      tally
+    };
+    let mut stream = lint_stream(&config, scan_stream(base, &[], &[], &options));
+    let streamed = collect(&mut stream);
+    assert!(stream.errors().is_empty());
+    assert!(streamed.iter().any(|issue| issue.kind == IssueKind::OrphanTest));
+    assert_eq!(tally(streamed), tally(lint_filenames(&config, &expected).issues));
+  }
+
+  #[test]
+  fn scan_stream_reports_missing_base() {
+    let mut stream = scan_stream("does/not/exist", &[], &[], &ScanOptions::default());
+    assert!(collect(&mut stream).is_empty());
+    assert!(stream.outcome().unwrap().is_err());
+  }
+}
@@ -0,0 +1,128 @@
+use crate::config::FilenameLintConfig;
+use crate::fix::{apply_renames, plan_renames, Rename};
+use crate::linter::visitor::lint_filenames;
+use crate::linter::Issue;
+use crate::scan::scanner::{scan_dir, ScanOptions};
+use anyhow::Result;
+use std::collections::HashSet;
+use std::time::Duration;
+
+/// How long the watcher waits between two scans.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Finds files created under a directory by rescanning it on every [`poll`](Watcher::poll).
+///
+/// A new file is only reported once it has been seen by two polls in a row, so files that an
+/// editor creates and removes right away while saving are never reported.
+pub struct Watcher {
+  base: String,
+  ignore: Vec<String>,
+  include: Vec<String>,
+  options: ScanOptions,
+  known: HashSet<String>,
+  pending: HashSet<String>,
+}
+
+impl Watcher {
+  /// Start watching `base`; the files already there are not reported.
+  pub fn new(base: &str, config: &FilenameLintConfig, options: ScanOptions) -> Result<Self> {
+    let known = scan_dir(base, &config.ignore, &config.include, &options)?.into_iter().collect();
+    Ok(Watcher {
+      base: base.to_string(),
+      ignore: config.ignore.clone(),
+      include: config.include.clone(),
+      options,
+      known,
+      pending: HashSet::new(),
+    })
+  }
+
+  /// Rescan, returning the files created since the poll before the previous one.
+  pub fn poll(&mut self) -> Result<Vec<String>> {
+    let current = scan_dir(&self.base, &self.ignore, &self.include, &self.options)?;
+    let mut created = vec![];
+    let mut pending = HashSet::new();
+    for file in &current {
+      if self.known.contains(file) {
+        continue;
+      }
+      match self.pending.contains(file) {
+        true => created.push(file.clone()),
+        false => {
+          pending.insert(file.clone());
+        }
+      }
+    }
+    self.known = current.into_iter().filter(|file| !pending.contains(file)).collect();
+    self.pending = pending;
+    created.sort();
+    Ok(created)
+  }
+
+  /// Treat `path` as already seen, e.g. the target of a rename the watcher made itself, so that
+  /// it is not reported as created.
+  pub fn mark_known(&mut self, path: &str) {
+    self.known.insert(path.to_string());
+  }
+
+  /// The files currently known to exist.
+  pub fn files(&self) -> Vec<String> {
+    self.known.iter().cloned().collect()
+  }
+}
+
+/// Poll once and lint the created files. With `fix`, issues are renamed away and the renames
+/// are returned instead; a renamed file does not come back as a created one.
+pub fn watch_step(
+  watcher: &mut Watcher,
+  config: &FilenameLintConfig,
+  fix: bool,
+) -> Result<(Vec<Issue>, Vec<Rename>)> {
+  let created = watcher.poll()?;
+  if created.is_empty() {
+    return Ok((vec![], vec![]));
+  }
+  // lint every file so checks relating files to each other see the whole tree, but only
+  // report the new ones
+  let mut issues = lint_filenames(config, &watcher.files()).issues;
+  issues.retain(|issue| created.contains(&issue.path));
+  if !fix {
+    return Ok((issues, vec![]));
+  }
+  let renamed = apply_renames(plan_renames(&issues, config.normalize_extension_case))?;
+  renamed.iter().for_each(|rename| watcher.mark_known(&rename.to));
+  issues.retain(|issue| !renamed.iter().any(|rename| rename.from == issue.path));
+  Ok((issues, renamed))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::config::FilenameCase;
+  use indexmap::IndexMap;
+  use std::fs;
+
+  #[test]
+  fn test_watch_fix_renames_once() {
+    let dir = tempfile::tempdir().unwrap();
+    let base = dir.path().to_str().unwrap();
+    fs::write(dir.path().join("OldName.ts"), "").unwrap();
+    let config = FilenameLintConfig {
+      ls: IndexMap::from([(".ts".to_string(), vec![FilenameCase::Kebab].into())]),
+      ..Default::default()
+    };
+    let mut watcher = Watcher::new(base, &config, ScanOptions::default()).unwrap();
+
+    fs::write(dir.path().join("BadName.ts"), "").unwrap();
+    let mut renames = vec![];
+    for _ in 0..4 {
+      let (issues, renamed) = watch_step(&mut watcher, &config, true).unwrap();
+      assert!(issues.is_empty());
+      renames.extend(renamed);
+    }
+    assert_eq!(renames.len(), 1);
+    assert_eq!(renames[0].to, format!("{}/bad-name.ts", base));
+    assert!(dir.path().join("bad-name.ts").exists());
+    assert!(dir.path().join("OldName.ts").exists(), "files from before the watch are left alone");
+  }
+}
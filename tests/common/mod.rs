@@ -0,0 +1,44 @@
+//! Helpers shared by the integration tests. Each test crate uses only some of them.
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Run fnlint in `dir`, ignoring any `FNLINT_CONFIG` of the environment running the tests.
+pub fn run(dir: &Path, args: &[&str]) -> Output {
+  Command::new(env!("CARGO_BIN_EXE_fnlint"))
+    .args(args)
+    .current_dir(dir)
+    .env_remove("FNLINT_CONFIG")
+    .output()
+    .unwrap()
+}
+
+/// Run git in `dir`, telling whether it succeeded. False as well when git is not installed.
+pub fn git(dir: &Path, args: &[&str]) -> bool {
+  Command::new("git").args(args).current_dir(dir).output().is_ok_and(|out| out.status.success())
+}
+
+/// A project with `config` as its `fnlint.config.json` and an empty file at each of `files`.
+pub fn project(config: &str, files: &[&str]) -> tempfile::TempDir {
+  let dir = tempfile::tempdir().unwrap();
+  fs::write(dir.path().join("fnlint.config.json"), config).unwrap();
+  for file in files {
+    let path = dir.path().join(file);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, "").unwrap();
+  }
+  dir
+}
+
+/// The issues of an `--format ndjson` run.
+pub fn ndjson(output: &Output) -> Vec<serde_json::Value> {
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  stdout.lines().map(|line| serde_json::from_str(line).unwrap()).collect()
+}
+
+/// The paths of the issues of an `--format ndjson` run.
+pub fn ndjson_paths(output: &Output) -> Vec<serde_json::Value> {
+  ndjson(output).into_iter().map(|issue| issue["path"].clone()).collect()
+}
@@ -0,0 +1,17 @@
+mod common;
+
+use common::{project, run};
+
+#[test]
+fn empty_rules_warn_or_fail() {
+  let dir = project(r#"{ "ls": {}, "ignore": [] }"#, &["BadName.ts"]);
+
+  let output = run(dir.path(), &[]);
+  assert_eq!(output.status.code(), Some(0));
+  let stderr = String::from_utf8_lossy(&output.stderr);
+  assert!(stderr.contains("Warning: ") && stderr.contains("defines no `ls` rules"), "{}", stderr);
+
+  let output = run(dir.path(), &["--strict-config"]);
+  assert_eq!(output.status.code(), Some(2));
+  assert!(String::from_utf8_lossy(&output.stderr).contains("defines no `ls` rules"));
+}
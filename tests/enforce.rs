@@ -0,0 +1,21 @@
+mod common;
+
+use common::{ndjson, project, run};
+
+#[test]
+fn enforce_overrides_rule_cases() {
+  let dir = project(
+    r#"{ "ls": { ".rs": "snake_case" }, "ignore": ["fnlint.config.json"] }"#,
+    &["user_service.rs", "README.md"],
+  );
+
+  assert_eq!(run(dir.path(), &[]).status.code(), Some(0));
+
+  let output = run(dir.path(), &["--enforce", "pascal", "--format", "ndjson"]);
+  assert_eq!(output.status.code(), Some(1));
+  let issues = ndjson(&output);
+  assert_eq!(issues.len(), 1, "{}", String::from_utf8_lossy(&output.stdout));
+  assert_eq!(issues[0]["path"], "user_service.rs");
+  assert_eq!(issues[0]["expected"], serde_json::json!(["Pascal"]));
+  assert!(dir.path().join("user_service.rs").exists());
+}
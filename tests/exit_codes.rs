@@ -0,0 +1,43 @@
+mod common;
+
+use common::{project, run};
+use std::fs;
+
+/// Asks for kebab-case `.ts` files.
+const CONFIG: &str = r#"{ "ls": { ".ts": "kebab-case" }, "ignore": [] }"#;
+
+#[test]
+fn clean_run_exits_0() {
+  let dir = project(CONFIG, &["order-item.ts"]);
+  assert_eq!(run(dir.path(), &[]).status.code(), Some(0));
+}
+
+#[test]
+fn issues_exit_1() {
+  let dir = project(CONFIG, &["OrderItem.ts"]);
+  assert_eq!(run(dir.path(), &[]).status.code(), Some(1));
+}
+
+#[test]
+fn config_errors_exit_2() {
+  let dir = tempfile::tempdir().unwrap();
+  let output = run(dir.path(), &[]);
+  assert_eq!(output.status.code(), Some(2));
+  assert!(String::from_utf8_lossy(&output.stderr).contains("No configuration file found"));
+
+  fs::write(dir.path().join("fnlint.config.json"), "{ not json").unwrap();
+  let output = run(dir.path(), &[]);
+  assert_eq!(output.status.code(), Some(2));
+  assert!(String::from_utf8_lossy(&output.stderr).contains("Cannot load the configuration"));
+
+  let dir = project(CONFIG, &["OrderItem.ts"]);
+  assert_eq!(run(dir.path(), &["--format", "diff"]).status.code(), Some(2));
+  assert_eq!(run(dir.path(), &["--no-such-flag"]).status.code(), Some(2));
+}
+
+#[test]
+fn other_errors_exit_4() {
+  let dir = project(CONFIG, &[]);
+  let output = run(dir.path(), &["--changed-manifest", "missing.json"]);
+  assert_eq!(output.status.code(), Some(4));
+}
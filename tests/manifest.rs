@@ -0,0 +1,23 @@
+mod common;
+
+use common::{ndjson_paths, project, run};
+use std::fs;
+
+#[test]
+fn lints_only_manifest_files() {
+  let dir = project(
+    r#"{ "ls": { ".ts": "kebab-case" }, "ignore": ["fnlint.config.json", "changed.json"] }"#,
+    &["src/UserService.ts", "src/OrderItem.ts", "src/cart-item.ts"],
+  );
+  fs::write(
+    dir.path().join("changed.json"),
+    r#"["src/UserService.ts", "src/cart-item.ts", "src/RemovedFile.ts"]"#,
+  )
+  .unwrap();
+
+  let output = run(dir.path(), &["--changed-manifest", "changed.json", "--format", "ndjson"]);
+  assert_eq!(output.status.code(), Some(1));
+  assert_eq!(ndjson_paths(&output), vec!["src/UserService.ts"]);
+  let stderr = String::from_utf8_lossy(&output.stderr);
+  assert!(stderr.contains("skipping src/RemovedFile.ts from the manifest"), "{}", stderr);
+}
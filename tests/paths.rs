@@ -0,0 +1,33 @@
+mod common;
+
+use common::{git, ndjson_paths, project, run};
+
+#[test]
+fn reported_paths_have_no_dot_prefix() {
+  let dir = project(
+    r#"{ "ls": { ".ts": ["kebab-case"] }, "ignore": ["fnlint.config.json"] }"#,
+    &["src/UserService.ts", "BadName.ts"],
+  );
+
+  let output = run(dir.path(), &["--format", "ndjson"]);
+  assert_eq!(output.status.code(), Some(1));
+  let mut paths = ndjson_paths(&output);
+  paths.sort_by_key(|path| path.to_string());
+  assert_eq!(paths, vec!["BadName.ts", "src/UserService.ts"]);
+}
+
+#[test]
+fn git_relative_paths_from_subdirectory() {
+  let dir = project(
+    r#"{ "ls": { ".ts": ["kebab-case"] }, "ignore": [] }"#,
+    &["packages/web/src/BadName.ts"],
+  );
+  if !git(dir.path(), &["init", "-q"]) {
+    eprintln!("git is not available, skipping");
+    return;
+  }
+
+  let output = run(&dir.path().join("packages/web"), &["--format", "ndjson", "--git-relative"]);
+  assert_eq!(output.status.code(), Some(1));
+  assert_eq!(ndjson_paths(&output), vec!["packages/web/src/BadName.ts"]);
+}
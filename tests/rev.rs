@@ -0,0 +1,25 @@
+mod common;
+
+use common::{git, project, run};
+
+#[test]
+fn lint_historical_revision() {
+  let dir = project(r#"{ "ls": { ".ts": ["kebab-case"] }, "ignore": [] }"#, &["BadName.ts"]);
+  if !git(dir.path(), &["init", "-q"]) {
+    eprintln!("git is not available, skipping");
+    return;
+  }
+  let commit = ["-c", "user.name=test", "-c", "user.email=test@example.com", "commit", "-qm"];
+  assert!(git(dir.path(), &["add", "."]));
+  assert!(git(dir.path(), &[&commit[..], &["old"]].concat()));
+  assert!(git(dir.path(), &["tag", "v1"]));
+  assert!(git(dir.path(), &["mv", "BadName.ts", "good-name.ts"]));
+  assert!(git(dir.path(), &[&commit[..], &["fix"]].concat()));
+
+  let output = run(dir.path(), &["--rev", "v1"]);
+  assert_eq!(output.status.code(), Some(1));
+  assert!(String::from_utf8_lossy(&output.stdout).contains("BadName"));
+
+  assert!(run(dir.path(), &["--rev", "HEAD"]).status.success());
+  assert!(!run(dir.path(), &["--rev", "no-such-ref"]).status.success());
+}
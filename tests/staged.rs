@@ -0,0 +1,24 @@
+mod common;
+
+use common::{git, project, run};
+
+#[test]
+fn staged_violation_blocks_commit() {
+  let dir = project(
+    r#"{ "ls": { ".ts": ["kebab-case"] }, "ignore": [] }"#,
+    &["good-name.ts", "BadName.ts"],
+  );
+  if !git(dir.path(), &["init", "-q"]) {
+    eprintln!("git is not available, skipping");
+    return;
+  }
+
+  assert!(git(dir.path(), &["add", "good-name.ts"]));
+  let output = run(dir.path(), &["--staged"]);
+  assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+
+  assert!(git(dir.path(), &["add", "BadName.ts"]));
+  let output = run(dir.path(), &["--staged"]);
+  assert_eq!(output.status.code(), Some(1));
+  assert!(String::from_utf8_lossy(&output.stdout).contains("BadName"));
+}
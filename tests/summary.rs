@@ -0,0 +1,20 @@
+mod common;
+
+use common::{project, run};
+use std::fs;
+
+#[test]
+fn summary_only_prints_one_line() {
+  let files = ["src/UserService.ts", "src/OrderItem.ts", "BadName.ts", "src/ok-name.ts"];
+  let dir =
+    project(r#"{ "ls": { ".ts": "kebab-case" }, "ignore": ["fnlint.config.json"] }"#, &files);
+
+  let output = run(dir.path(), &["--format", "summary-only"]);
+  assert_eq!(output.status.code(), Some(1));
+  assert_eq!(String::from_utf8_lossy(&output.stdout), "fnlint: 3 issues across 3 files\n");
+
+  files[..3].iter().for_each(|file| fs::remove_file(dir.path().join(file)).unwrap());
+  let output = run(dir.path(), &["--format", "summary-only"]);
+  assert_eq!(output.status.code(), Some(0));
+  assert_eq!(String::from_utf8_lossy(&output.stdout), "fnlint: 0 issues across 0 files\n");
+}
@@ -0,0 +1,25 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::{ndjson_paths, project, run};
+use std::fs;
+use std::os::unix::fs::symlink;
+
+#[test]
+fn symlinks_are_linted_by_link_name() {
+  let dir = project(
+    r#"{ "ls": { ".ts": "kebab-case" }, "ignore": ["fnlint.config.json"], "followSymlinks": true }"#,
+    &["src/user-service.ts"],
+  );
+  let outside = tempfile::tempdir().unwrap();
+  symlink("user-service.ts", dir.path().join("src/UserService.ts")).unwrap();
+  fs::write(outside.path().join("shared-types.ts"), "").unwrap();
+  symlink(outside.path(), dir.path().join("shared")).unwrap();
+
+  let output = run(dir.path(), &["--format", "ndjson"]);
+  assert_eq!(output.status.code(), Some(1));
+  assert_eq!(ndjson_paths(&output), vec!["src/UserService.ts"]);
+  let stderr = String::from_utf8_lossy(&output.stderr);
+  assert!(stderr.contains("Symlink shared points outside the scan root"), "{}", stderr);
+}